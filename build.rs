@@ -0,0 +1,44 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let mut days = Vec::new();
+    for year_entry in fs::read_dir("src").unwrap() {
+        let year_entry = year_entry.unwrap();
+        let year_name = year_entry.file_name().to_string_lossy().to_string();
+        let year = match year_name.strip_prefix('y').map(str::parse::<u16>) {
+            Some(Ok(year)) if year_entry.path().is_dir() => year,
+            _ => continue,
+        };
+        for day_entry in fs::read_dir(year_entry.path()).unwrap() {
+            let day_name = day_entry.unwrap().file_name().to_string_lossy().to_string();
+            let day = day_name
+                .strip_prefix("day")
+                .and_then(|name| name.strip_suffix(".rs"))
+                .map(str::parse::<u8>);
+            if let Some(Ok(day)) = day {
+                days.push((year, day));
+            }
+        }
+    }
+    days.sort();
+
+    let mut table = String::from("&[\n");
+    let mut answer_tests = String::new();
+    for (year, day) in days {
+        table.push_str(&format!(
+            "    Entry {{ year: {year}, day: {day}, solution: &crate::y{year}::day{day}::Solver }},\n"
+        ));
+        answer_tests.push_str(&format!(
+            "#[test]\nfn y{year}_day{day}() {{\n    verify({year}, {day});\n}}\n\n"
+        ));
+    }
+    table.push(']');
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), table).unwrap();
+    fs::write(Path::new(&out_dir).join("answer_tests.rs"), answer_tests).unwrap();
+}
@@ -0,0 +1,138 @@
+use crate::error::{Error, Result};
+use crate::util::coordinate::Coordinate;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Answer {
+    /// Wide enough for any signed or unsigned 64-bit answer
+    Number(i128),
+    Text(String),
+    Lines(Vec<String>),
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        match value.contains('\n') {
+            true => Answer::Lines(value.lines().map(str::to_string).collect()),
+            false => Answer::Text(value),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::from(value.to_string())
+    }
+}
+
+impl From<Coordinate> for Answer {
+    fn from(value: Coordinate) -> Self {
+        Answer::Text(format!("{},{}", value.x, value.y))
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+/// Converts what a part returns, plain or fallible, into a `Result<Answer>`.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>, E: Into<Error>> IntoAnswer for std::result::Result<T, E> {
+    fn into_answer(self) -> Result<Answer> {
+        self.map(Into::into).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_number() {
+        assert_eq!(Answer::from(42usize), Answer::Number(42));
+        assert_eq!(Answer::from(-3i32), Answer::Number(-3));
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from(i64::MIN).to_string(), "-9223372036854775808");
+    }
+
+    #[test]
+    fn test_from_text() {
+        assert_eq!(Answer::from("1,2,3"), Answer::Text("1,2,3".to_string()));
+        assert_eq!(
+            Answer::from("#.\n.#"),
+            Answer::Lines(vec!["#.".to_string(), ".#".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_from_coordinate() {
+        assert_eq!(
+            Answer::from(Coordinate::new(1, 6)),
+            Answer::Text("6,1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_option() {
+        assert_eq!(Answer::from(Some(7u64)), Answer::Number(7));
+        assert_eq!(Answer::from(None::<u64>), Answer::Unsolved);
+    }
+
+    #[test]
+    fn test_into_answer() {
+        assert_eq!(5usize.into_answer().unwrap(), Answer::Number(5));
+        let parsed = "12".parse::<u32>().into_answer();
+        assert_eq!(parsed.unwrap(), Answer::Number(12));
+        assert!("x".parse::<u32>().into_answer().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::Number(12).to_string(), "12");
+        assert_eq!(Answer::Text("co,de".to_string()).to_string(), "co,de");
+        assert_eq!(
+            Answer::Lines(vec!["ab".to_string(), "cd".to_string()]).to_string(),
+            "ab\ncd"
+        );
+    }
+}
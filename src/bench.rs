@@ -0,0 +1,285 @@
+use crate::error::{Error, Result};
+use crate::runner;
+use crate::solution::Entry;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarizes the samples, or returns `None` if there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let nanos = sorted
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        let min = *sorted.first()?;
+        let mean = nanos.iter().sum::<f64>() / nanos.len() as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / nanos.len() as f64;
+        let mid = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[mid - 1] + sorted[mid]) / 2,
+            _ => sorted[mid],
+        };
+        Some(Stats {
+            min,
+            median,
+            mean: Duration::from_nanos(mean as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub iterations: usize,
+    /// Time spent in the day's parser alone, if it has one
+    pub parse: Option<Stats>,
+    /// Time spent in the whole part, parsing included
+    pub total: Stats,
+}
+
+impl Measurement {
+    /// Median time spent solving, i.e. the part's median without the parser's median.
+    pub fn solve_median(&self) -> Duration {
+        match self.parse {
+            Some(parse) => self.total.median.saturating_sub(parse.median),
+            None => self.total.median,
+        }
+    }
+}
+
+pub fn bench(entry: &Entry, part: u8, input: &str, iterations: usize) -> Result<Measurement> {
+    let no_samples = || Error::Usage("--iterations must be at least 1".to_string());
+    // Checked up front so a bad count fails before the warm-up run
+    if iterations == 0 {
+        return Err(no_samples());
+    }
+    let stats = |samples: Vec<Duration>| Stats::from_samples(&samples).ok_or_else(no_samples);
+    let solve = || entry.solution.part(part, input, &[]);
+    // Warm-up run, which also makes sure the part succeeds before it's measured
+    runner::catch_panic(solve)?;
+    let parse = match entry.solution.parse(input) {
+        true => Some(stats(sample(iterations, || {
+            entry.solution.parse(input);
+        }))?),
+        false => None,
+    };
+    let total = stats(sample(iterations, || drop(black_box(solve()))))?;
+    Ok(Measurement {
+        year: entry.year,
+        day: entry.day,
+        part,
+        iterations,
+        parse,
+        total,
+    })
+}
+
+fn sample(iterations: usize, mut f: impl FnMut()) -> Vec<Duration> {
+    (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Relative change of the part's median time compared to a baseline, in percent. `None` if the
+/// baseline's median is zero, since there's nothing to compare with.
+pub fn delta_percent(baseline: &Measurement, current: &Measurement) -> Option<f64> {
+    if baseline.total.median.is_zero() {
+        return None;
+    }
+    let before = baseline.total.median.as_nanos() as f64;
+    let after = current.total.median.as_nanos() as f64;
+    Some((after - before) / before * 100.0)
+}
+
+pub fn to_json(measurements: &[Measurement]) -> String {
+    let mut json = String::from("[\n");
+    for (i, m) in measurements.iter().enumerate() {
+        let _ = write!(
+            json,
+            "  {{\"year\": {}, \"day\": {}, \"part\": {}, \"iterations\": {}",
+            m.year, m.day, m.part, m.iterations
+        );
+        if let Some(parse) = &m.parse {
+            write_stats(&mut json, "parse", parse);
+        }
+        write_stats(&mut json, "total", &m.total);
+        json.push('}');
+        if i + 1 < measurements.len() {
+            json.push(',');
+        }
+        json.push('\n');
+    }
+    json.push(']');
+    json.push('\n');
+    json
+}
+
+fn write_stats(json: &mut String, prefix: &str, stats: &Stats) {
+    let _ = write!(
+        json,
+        ", \"{prefix}_min_ns\": {}, \"{prefix}_median_ns\": {}, \"{prefix}_mean_ns\": {}, \"{prefix}_stddev_ns\": {}",
+        stats.min.as_nanos(),
+        stats.median.as_nanos(),
+        stats.mean.as_nanos(),
+        stats.stddev.as_nanos()
+    );
+}
+
+/// Reads back the flat JSON array written by [`to_json`].
+pub fn from_json(json: &str) -> Result<Vec<Measurement>> {
+    let invalid = || Error::parse("invalid baseline");
+    let body = json
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(invalid)?;
+    body.split('}')
+        .map(|record| record.trim().trim_start_matches(',').trim())
+        .filter(|record| !record.is_empty())
+        .map(|record| {
+            let fields = record
+                .strip_prefix('{')
+                .ok_or_else(invalid)?
+                .split(',')
+                .map(|field| {
+                    let (key, value) = field.split_once(':').ok_or_else(invalid)?;
+                    let value = value.trim().parse::<u64>().map_err(|_| invalid())?;
+                    Ok((key.trim().trim_matches('"'), value))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let field = |name: &str| {
+                fields
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| *value)
+            };
+            let stats = |prefix: &str| -> Option<Stats> {
+                Some(Stats {
+                    min: Duration::from_nanos(field(&format!("{prefix}_min_ns"))?),
+                    median: Duration::from_nanos(field(&format!("{prefix}_median_ns"))?),
+                    mean: Duration::from_nanos(field(&format!("{prefix}_mean_ns"))?),
+                    stddev: Duration::from_nanos(field(&format!("{prefix}_stddev_ns"))?),
+                })
+            };
+            Ok(Measurement {
+                year: field("year").ok_or_else(invalid)? as u16,
+                day: field("day").ok_or_else(invalid)? as u8,
+                part: field("part").ok_or_else(invalid)? as u8,
+                iterations: field("iterations").ok_or_else(invalid)? as usize,
+                parse: stats("parse"),
+                total: stats("total").ok_or_else(invalid)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(6), ms(8)]).unwrap();
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(2236067));
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_bench() {
+        let entry = solution::find(2024, 1).unwrap();
+        let measurement = bench(entry, 2, "3   4\n4   3\n2   5", 3).unwrap();
+        assert_eq!(
+            (measurement.year, measurement.day, measurement.part),
+            (2024, 1, 2)
+        );
+        assert_eq!(measurement.iterations, 3);
+        assert!(measurement.parse.is_some());
+        assert!(bench(solution::find(2015, 1).unwrap(), 1, "(", 1)
+            .unwrap()
+            .parse
+            .is_none());
+        assert!(matches!(bench(entry, 1, "3   4", 0), Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn test_delta_percent() {
+        let stats = |median| Stats {
+            min: median,
+            median,
+            mean: median,
+            stddev: Duration::ZERO,
+        };
+        let measurement = |median| Measurement {
+            year: 2024,
+            day: 16,
+            part: 1,
+            iterations: 1,
+            parse: None,
+            total: stats(median),
+        };
+        assert_eq!(
+            delta_percent(&measurement(ms(200)), &measurement(ms(150))),
+            Some(-25.0)
+        );
+        assert_eq!(
+            delta_percent(&measurement(Duration::ZERO), &measurement(ms(150))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let stats = Stats {
+            min: ms(1),
+            median: ms(2),
+            mean: ms(3),
+            stddev: ms(4),
+        };
+        let measurements = vec![
+            Measurement {
+                year: 2024,
+                day: 16,
+                part: 1,
+                iterations: 10,
+                parse: Some(stats),
+                total: stats,
+            },
+            Measurement {
+                year: 2015,
+                day: 1,
+                part: 2,
+                iterations: 5,
+                parse: None,
+                total: stats,
+            },
+        ];
+        assert_eq!(from_json(&to_json(&measurements)).unwrap(), measurements);
+        assert!(from_json("{").is_err());
+    }
+}
@@ -0,0 +1,472 @@
+use crate::error::{Error, Result};
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "aoc-rust (github.com/Dunklas/advent-of-code)";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends requests for [`Client`], so tests can swap the network for a local server.
+pub trait Http {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response>;
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<Response>;
+}
+
+/// Plain HTTP/1.1 over a `TcpStream`, for `http://` endpoints such as a local stand-in server.
+pub struct TcpHttp;
+
+impl TcpHttp {
+    fn send(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &str,
+    ) -> Result<Response> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| Error::Http(format!("unsupported url {}", url)))?;
+        let (host, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n",
+            method,
+            path,
+            host,
+            body.len()
+        );
+        for (name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+
+        let address = match host.contains(':') {
+            true => host.to_string(),
+            false => format!("{}:80", host),
+        };
+        let mut stream = TcpStream::connect(address)?;
+        stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+        stream.write_all(request.as_bytes())?;
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw)?;
+        parse_response(&String::from_utf8_lossy(&raw))
+    }
+}
+
+impl Http for TcpHttp {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response> {
+        self.send("GET", url, headers, "")
+    }
+
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<Response> {
+        self.send("POST", url, headers, body)
+    }
+}
+
+fn parse_response(raw: &str) -> Result<Response> {
+    let invalid = || Error::Http("invalid response".to_string());
+    let (head, body) = raw.split_once("\r\n\r\n").ok_or_else(invalid)?;
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(invalid)?;
+    let chunked = lines.any(|line| {
+        line.to_ascii_lowercase()
+            .replace(' ', "")
+            .starts_with("transfer-encoding:chunked")
+    });
+    let body = match chunked {
+        true => decode_chunked(body).ok_or_else(invalid)?,
+        false => body.to_string(),
+    };
+    Ok(Response { status, body })
+}
+
+fn decode_chunked(mut body: &str) -> Option<String> {
+    let mut decoded = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n")?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.push_str(rest.get(..size)?);
+        body = rest.get(size..)?.strip_prefix("\r\n")?;
+    }
+}
+
+/// Shells out to `curl`, for `https://` endpoints. Headers and body go through stdin, keeping the
+/// session token out of the process list.
+pub struct CurlHttp;
+
+impl CurlHttp {
+    fn send(&self, url: &str, headers: &[(&str, &str)], body: Option<&str>) -> Result<Response> {
+        let mut config = format!("url = {}\n", quote(url));
+        for (name, value) in headers {
+            config.push_str(&format!(
+                "header = {}\n",
+                quote(&format!("{}: {}", name, value))
+            ));
+        }
+        if let Some(body) = body {
+            config.push_str(&format!("data-binary = {}\n", quote(body)));
+        }
+        let mut child = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--write-out",
+                "\n%{http_code}",
+                "--config",
+                "-",
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::Http(format!("failed to run curl: {}", e)))?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(config.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(Error::Http(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or_else(|| Error::Http("invalid response".to_string()))?;
+        Ok(Response {
+            status: status
+                .trim()
+                .parse()
+                .map_err(|_| Error::Http("invalid response".to_string()))?,
+            body: body.to_string(),
+        })
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+impl Http for CurlHttp {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<Response> {
+        self.send(url, headers, None)
+    }
+
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<Response> {
+        self.send(url, headers, Some(body))
+    }
+}
+
+/// Makes sure consecutive requests, across runs, are at least `interval` apart.
+pub struct RateLimiter {
+    pub path: PathBuf,
+    pub interval: Duration,
+}
+
+impl RateLimiter {
+    pub fn wait(&self) -> Result<()> {
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+        };
+        let last = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(remaining) = last.and_then(|last| (last + self.interval).checked_sub(now())) {
+            thread::sleep(remaining);
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, now().as_millis().to_string())?;
+        Ok(())
+    }
+}
+
+pub struct Client {
+    pub http: Box<dyn Http>,
+    pub base_url: String,
+    pub session: String,
+    pub limiter: RateLimiter,
+}
+
+impl Client {
+    /// Configured from the environment:
+    /// - `AOC_URL`: endpoint, defaults to [`DEFAULT_URL`]
+    /// - `AOC_SESSION`: session token, otherwise read from `AOC_SESSION_FILE` or `~/.config/aoc-rust/session`
+    /// - `AOC_HTTP`: `tcp` or `curl`, defaults to `tcp` for `http://` and `curl` for `https://` urls
+    ///
+    /// Requests are at least 5 seconds apart, tracked in `inputs/.last-request`.
+    pub fn from_env() -> Result<Client> {
+        let base_url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+        let backend =
+            env::var("AOC_HTTP").unwrap_or_else(|_| match base_url.starts_with("http://") {
+                true => "tcp".to_string(),
+                false => "curl".to_string(),
+            });
+        let http: Box<dyn Http> = match backend.as_str() {
+            "tcp" => Box::new(TcpHttp),
+            "curl" => Box::new(CurlHttp),
+            _ => {
+                return Err(Error::Usage(format!(
+                    "Invalid AOC_HTTP: {} (expected tcp or curl)",
+                    backend
+                )))
+            }
+        };
+        Ok(Client {
+            http,
+            base_url,
+            session: session()?,
+            limiter: RateLimiter {
+                path: PathBuf::from("inputs").join(".last-request"),
+                interval: Duration::from_secs(5),
+            },
+        })
+    }
+
+    pub fn get(&self, path: &str) -> Result<Response> {
+        self.limiter.wait()?;
+        let cookie = format!("session={}", self.session);
+        let headers = [("Cookie", cookie.as_str()), ("User-Agent", USER_AGENT)];
+        check(self.http.get(&self.url(path), &headers)?)
+    }
+
+    /// Posts `form` as `application/x-www-form-urlencoded`.
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<Response> {
+        self.limiter.wait()?;
+        let cookie = format!("session={}", self.session);
+        let headers = [
+            ("Cookie", cookie.as_str()),
+            ("User-Agent", USER_AGENT),
+            ("Content-Type", "application/x-www-form-urlencoded"),
+        ];
+        let body = form
+            .iter()
+            .map(|(key, value)| format!("{}={}", url_encode(key), url_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        check(self.http.post(&self.url(path), &headers, &body)?)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url.trim_end_matches('/'), path)
+    }
+}
+
+fn check(response: Response) -> Result<Response> {
+    match response.status {
+        200..=299 => Ok(response),
+        status => Err(Error::Http(format!(
+            "server returned {}: {}",
+            status,
+            response.body.lines().next().unwrap_or_default()
+        ))),
+    }
+}
+
+fn session() -> Result<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+    let path = match env::var("AOC_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => Path::new(&env::var("HOME").unwrap_or_default()).join(".config/aoc-rust/session"),
+    };
+    match fs::read_to_string(&path) {
+        Ok(session) => Ok(session.trim().to_string()),
+        Err(_) => Err(Error::Usage(format!(
+            "No session token, set AOC_SESSION or write it to {}",
+            path.display()
+        ))),
+    }
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    /// A local stand-in server answering every request with the next of `responses`,
+    /// recording the raw requests it received.
+    pub struct MockServer {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockServer {
+        pub fn start(responses: Vec<(u16, &str)>) -> MockServer {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let received = Arc::clone(&requests);
+            let responses = responses
+                .into_iter()
+                .map(|(status, body)| (status, body.to_string()))
+                .collect::<Vec<_>>();
+            thread::spawn(move || {
+                for (status, body) in responses {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    received.lock().unwrap().push(read_request(&mut stream));
+                    let response = format!(
+                        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    stream.write_all(response.as_bytes()).unwrap();
+                }
+            });
+            MockServer { url, requests }
+        }
+
+        pub fn client(&self, name: &str) -> Client {
+            Client {
+                http: Box::new(TcpHttp),
+                base_url: self.url.clone(),
+                session: "secret".to_string(),
+                limiter: RateLimiter {
+                    path: env::temp_dir().join(format!("aoc-rust-{}-last-request", name)),
+                    interval: Duration::ZERO,
+                },
+            }
+        }
+
+        pub fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        loop {
+            let read = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .and_then(|length| length.parse::<usize>().ok())
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    return text.to_string();
+                }
+            }
+            if read == 0 {
+                return text.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockServer;
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let response = parse_response("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello").unwrap();
+        assert_eq!(
+            response,
+            Response {
+                status: 200,
+                body: "hello".to_string()
+            }
+        );
+        let chunked = parse_response(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(chunked.body, "hello world");
+        assert!(parse_response("garbage").is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let server = MockServer::start(vec![(200, "1 2 3\n"), (404, "Not Found\n")]);
+        let client = server.client("get");
+        assert_eq!(client.get("/2024/day/1/input").unwrap().body, "1 2 3\n");
+        let error = client.get("/2024/day/26/input").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "request failed: server returned 404: Not Found"
+        );
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=secret\r\n"));
+    }
+
+    #[test]
+    fn test_post() {
+        let server = MockServer::start(vec![(200, "ok")]);
+        let client = server.client("post");
+        client
+            .post("/2024/day/1/answer", &[("level", "1"), ("answer", "a b&c")])
+            .unwrap();
+        let request = &server.requests()[0];
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=a+b%26c"));
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter {
+            path: env::temp_dir().join("aoc-rust-rate-limiter"),
+            interval: Duration::from_millis(200),
+        };
+        let _ = fs::remove_file(&limiter.path);
+        let start = std::time::Instant::now();
+        limiter.wait().unwrap();
+        limiter.wait().unwrap();
+        // The last request is stored with millisecond precision
+        assert!(start.elapsed() >= Duration::from_millis(199));
+        fs::remove_file(&limiter.path).unwrap();
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+/// CPU time used so far by the calling thread, or `None` where it can't be measured. Unlike wall time
+/// it doesn't grow while the thread waits for a core, so it stays comparable when days run in parallel.
+#[cfg(target_os = "linux")]
+pub fn thread_time() -> Option<Duration> {
+    use std::os::raw::{c_int, c_long};
+
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: c_long,
+        tv_nsec: c_long,
+    }
+
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 3;
+
+    extern "C" {
+        fn clock_gettime(clock: c_int, time: *mut Timespec) -> c_int;
+    }
+
+    let mut time = Timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // Safety: `time` is a valid, writable timespec for the duration of the call
+    match unsafe { clock_gettime(CLOCK_THREAD_CPUTIME_ID, &mut time) } {
+        0 => Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32)),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn thread_time() -> Option<Duration> {
+    None
+}
+
+/// Runs `f`, returning its result with the CPU time the calling thread spent in it.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Duration>) {
+    let start = thread_time();
+    let result = f();
+    let used = start
+        .zip(thread_time())
+        .map(|(start, end)| end.saturating_sub(start));
+    (result, used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_measure() {
+        let (_, used) = measure(|| {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(5) {}
+        });
+        assert!(used.unwrap() >= Duration::from_millis(4));
+        let (_, slept) = measure(|| std::thread::sleep(Duration::from_millis(20)));
+        assert!(slept.unwrap() < Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::util::grid::ParseGridError;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::num::ParseIntError;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be parsed, `line` is 1-based when known
+    Parse {
+        line: Option<usize>,
+        message: String,
+    },
+    /// The input parsed fine, but has no answer
+    NoAnswer(String),
+    /// A solution panicked instead of returning an error
+    Panic(String),
+    Io(io::Error),
+    /// An input file could not be read
+    Input {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Usage(String),
+    /// A request to the puzzle server failed
+    Http(String),
+    /// An answer was not submitted, as earlier attempts show it can't be right
+    Submit(String),
+    Day {
+        year: u16,
+        day: u8,
+        source: Box<Error>,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(message: impl Into<String>) -> Error {
+        Error::Parse {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// Attaches a 1-based line number to a parse error that has none yet.
+    pub fn at_line(self, line: usize) -> Error {
+        match self {
+            Error::Parse {
+                line: None,
+                message,
+            } => Error::Parse {
+                line: Some(line),
+                message,
+            },
+            error => error,
+        }
+    }
+
+    pub fn in_day(self, year: u16, day: u8) -> Error {
+        Error::Day {
+            year,
+            day,
+            source: Box::new(self),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse {
+                line: Some(line),
+                message,
+            } => write!(f, "failed to parse line {}: {}", line, message),
+            Error::Parse {
+                line: None,
+                message,
+            } => write!(f, "failed to parse input: {}", message),
+            Error::NoAnswer(message) => write!(f, "no answer: {}", message),
+            Error::Panic(message) => write!(f, "solution panicked: {}", message),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Input { path, source } => {
+                write!(f, "failed to read input {}: {}", path.display(), source)
+            }
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Http(message) => write!(f, "request failed: {}", message),
+            Error::Submit(message) => write!(f, "not submitting: {}", message),
+            Error::Day { year, day, source } => write!(f, "{} day {}: {}", year, day, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(value: ParseIntError) -> Self {
+        Error::parse(value.to_string())
+    }
+}
+
+impl From<ParseGridError> for Error {
+    fn from(value: ParseGridError) -> Self {
+        Error::parse(value.to_string())
+    }
+}
+
+/// Parses every line with `f`, tagging errors with the line they occurred on.
+pub fn parse_lines<T, E: Into<Error>>(
+    input: &str,
+    mut f: impl FnMut(&str) -> std::result::Result<T, E>,
+) -> Result<Vec<T>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.into().at_line(i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let numbers = parse_lines("1\n2\n3", |line| line.parse::<u8>()).unwrap();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        let error = parse_lines("1\nx\n3", |line| line.parse::<u8>()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse line 2: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_at_line_keeps_existing_line() {
+        let error = Error::parse("bad").at_line(3).at_line(7);
+        assert_eq!(error.to_string(), "failed to parse line 3: bad");
+    }
+
+    #[test]
+    fn test_in_day() {
+        let error = Error::NoAnswer("no path".to_string()).in_day(2024, 18);
+        assert_eq!(error.to_string(), "2024 day 18: no answer: no path");
+    }
+}
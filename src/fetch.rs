@@ -0,0 +1,72 @@
+use crate::client::Client;
+use crate::error::Result;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already on disk, nothing was requested
+    Cached,
+    Downloaded,
+}
+
+/// An input counts as cached once it has content, so the empty files created by `new` get filled in.
+pub fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Downloads the input of a day to `path`, unless it's already there.
+pub fn fetch(client: &Client, year: u16, day: u8, path: &Path) -> Result<Fetched> {
+    if is_cached(path) {
+        return Ok(Fetched::Cached);
+    }
+    let response = client.get(&format!("/{}/day/{}/input", year, day))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, response.body)?;
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockServer;
+    use std::env;
+
+    #[test]
+    fn test_fetch() {
+        let server = MockServer::start(vec![(200, "1 2\n3 4\n")]);
+        let client = server.client("fetch");
+        let path = env::temp_dir().join("aoc-rust-fetch/2024/1.in");
+        let _ = fs::remove_file(&path);
+        assert_eq!(fetch(&client, 2024, 1, &path).unwrap(), Fetched::Downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+        assert_eq!(fetch(&client, 2024, 1, &path).unwrap(), Fetched::Cached);
+        assert_eq!(server.requests().len(), 1);
+        assert!(server.requests()[0].starts_with("GET /2024/day/1/input "));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_replaces_empty_input() {
+        let server = MockServer::start(vec![(200, "input\n")]);
+        let path = env::temp_dir().join("aoc-rust-fetch-empty.in");
+        fs::write(&path, "").unwrap();
+        assert!(!is_cached(&path));
+        assert_eq!(
+            fetch(&server.client("fetch-empty"), 2024, 2, &path).unwrap(),
+            Fetched::Downloaded
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_error() {
+        let server = MockServer::start(vec![(400, "Please log in")]);
+        let path = env::temp_dir().join("aoc-rust-fetch-error.in");
+        let _ = fs::remove_file(&path);
+        assert!(fetch(&server.client("fetch-error"), 2024, 3, &path).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,19 @@
+pub mod answer;
+pub mod bench;
+pub mod client;
+pub mod cpu;
+pub mod error;
+pub mod fetch;
+pub mod memory;
+pub mod output;
+pub mod params;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod util;
+pub mod verify;
+pub mod watch;
+pub mod y2015;
+pub mod y2023;
+pub mod y2024;
@@ -1,176 +1,567 @@
-use std::{
-    env,
-    io::{self, Read},
-};
-
-mod util;
-mod y2015;
-mod y2023;
-mod y2024;
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let year = match args.get(1) {
-        Some(year) => year,
-        None => {
-            panic!("Missing year!")
-        }
-    };
-    let day = match args.get(2) {
-        Some(day) => day,
-        None => {
-            panic!("Missing day!")
+use aoc_rust::answer::Answer;
+use aoc_rust::bench::{self, Measurement};
+use aoc_rust::client::Client;
+use aoc_rust::error::{Error, Result};
+use aoc_rust::fetch;
+use aoc_rust::memory::{self, Usage};
+use aoc_rust::output::{self, Format};
+use aoc_rust::params;
+use aoc_rust::runner::{self, InputSource, Report};
+use aoc_rust::scaffold;
+use aoc_rust::solution::{self, Entry};
+use aoc_rust::submit::{self, History, Verdict};
+use aoc_rust::verify::{self, Verification};
+use aoc_rust::watch::{self, Watcher};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use std::{env, fs, process::ExitCode, thread};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("list") => {
+            list();
+            Ok(())
         }
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        _ => solve(&args),
     };
-    let mut input = String::new();
-    match io::stdin().read_to_string(&mut input) {
-        Ok(_b) => {}
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
-            panic!("Failed to read input: {}", e);
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
         }
-    };
-    assert!(!input.is_empty());
-    match year.as_str() {
-        "2015" => match day.as_str() {
-            "1" => {
-                y2015::day1::solve(&input);
-            }
-            "2" => {
-                y2015::day2::solve(&input);
-            }
-            "3" => {
-                y2015::day3::solve(&input);
-            }
-            _ => {
-                panic!("Solution does not exist for {} {}", year, day);
-            }
-        },
-        "2023" => match day.as_str() {
-            "1" => {
-                y2023::day1::solve(&input);
-            }
-            "2" => {
-                y2023::day2::solve(&input);
-            }
-            "3" => {
-                y2023::day3::solve(&input);
-            }
-            "4" => {
-                y2023::day4::solve(&input);
-            }
-            "5" => {
-                y2023::day5::solve(&input);
-            }
-            "6" => {
-                y2023::day6::solve(&input);
-            }
-            "7" => {
-                y2023::day7::solve(&input);
-            }
-            "8" => {
-                y2023::day8::solve(&input);
-            }
-            "9" => {
-                y2023::day9::solve(&input);
-            }
-            "10" => {
-                y2023::day10::solve(&input);
-            }
-            "11" => {
-                y2023::day11::solve(&input);
-            }
-            "14" => {
-                y2023::day14::solve(&input);
-            }
-            "15" => {
-                y2023::day15::solve(&input);
-            }
-            "16" => {
-                y2023::day16::solve(&input);
-            }
-            "18" => {
-                y2023::day18::solve(&input);
-            }
-            "19" => {
-                y2023::day19::solve(&input);
-            }
-            _ => {
-                panic!("Solution does not exist for {} {}", year, day);
-            }
-        },
-        "2024" => match day.as_str() {
-            "1" => {
-                y2024::day1::solve(&input);
-            }
-            "2" => {
-                y2024::day2::solve(&input);
-            }
-            "3" => {
-                y2024::day3::solve(&input);
-            }
-            "4" => {
-                y2024::day4::solve(&input);
-            }
-            "5" => {
-                y2024::day5::solve(&input);
-            }
-            "6" => {
-                y2024::day6::solve(&input);
-            }
-            "7" => {
-                y2024::day7::solve(&input);
-            }
-            "8" => {
-                y2024::day8::solve(&input);
-            }
-            "9" => {
-                y2024::day9::solve(&input);
-            }
-            "10" => {
-                y2024::day10::solve(&input);
-            }
-            "11" => {
-                y2024::day11::solve(&input);
-            }
-            "12" => {
-                y2024::day12::solve(&input);
-            }
-            "13" => {
-                y2024::day13::solve(&input);
-            }
-            "14" => {
-                y2024::day14::solve(&input);
-            }
-            "15" => {
-                y2024::day15::solve(&input);
-            }
-            "16" => {
-                y2024::day16::solve(&input);
-            }
-            "17" => {
-                y2024::day17::solve(&input);
-            }
-            "18" => {
-                y2024::day18::solve(&input);
-            }
-            "19" => {
-                y2024::day19::solve(&input);
-            }
-            "20" => {
-                y2024::day20::solve(&input);
-            }
-            "21" => {
-                y2024::day21::solve(&input);
-            }
-            "23" => {
-                y2024::day23::solve(&input);
-            }
-            _ => {
-                panic!("Solution does not exist for {} {}", year, day);
+    }
+}
+
+fn solve(args: &[String]) -> Result<()> {
+    let entry = find_entry(args.first(), args.get(1))?;
+    let input = runner::read_input(entry.year, entry.day, &input_source(args)?)
+        .map_err(|e| e.in_day(entry.year, entry.day))?;
+    let format = parse_format(args)?;
+    let parts = parse_parts(args)?;
+    let params = flag_values(args, "--param")
+        .map(params::parse_override)
+        .collect::<Result<Vec<_>>>()?;
+    let mut report = runner::run_with(entry, &input, &parts, &params);
+    // Only the machine-readable formats report parse time
+    if format != Format::Text {
+        runner::time_parse(entry, &input, &mut report);
+    }
+    match format {
+        Format::Text => {
+            for result in &report.parts {
+                if let Ok(answer) = &result.answer {
+                    println!("Part {}: {}", result.part, answer);
+                }
+                if let Some(usage) = result.memory {
+                    println!("  memory: {}", describe_usage(usage));
+                }
             }
-        },
+        }
+        Format::Json => print!("{}", output::to_json(std::slice::from_ref(&report))),
+        Format::Csv => print!("{}\n{}", output::CSV_HEADER, output::to_csv(&report)),
+    }
+    let (year, day) = (report.year, report.day);
+    match report.parts.into_iter().find_map(|part| part.answer.err()) {
+        Some(e) => Err(e.in_day(year, day)),
+        None => Ok(()),
+    }
+}
+
+fn list() {
+    for year in solution::years() {
+        let days = solution::days(year)
+            .map(|entry| entry.day.to_string())
+            .collect::<Vec<_>>();
+        let missing = solution::missing(year)
+            .iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<_>>();
+        let params = solution::days(year)
+            .filter(|entry| !entry.solution.params().is_empty())
+            .map(|entry| format!("{} ({})", entry.day, entry.solution.params().join(", ")))
+            .collect::<Vec<_>>();
+        println!("{}: {}", year, days.join(", "));
+        if !missing.is_empty() {
+            println!("  missing: {}", missing.join(", "));
+        }
+        if !params.is_empty() {
+            println!("  params: {}", params.join(", "));
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let entries: Vec<&Entry> = match args.first().map(String::as_str) {
+        Some("--all") => solution::all().iter().collect(),
+        Some("--year") => solution::days(parse_arg(args.get(1), "year")?).collect(),
         _ => {
-            panic!("Solution does not exist for {} {}", year, day);
+            return Err(Error::Usage(
+                "Usage: run --year <YEAR> | --all [--example <N>] [--jobs <N>] [--format json|csv|text]\n\
+                 Ends with the sum of per-day CPU time and the wall time of the whole run"
+                    .to_string(),
+            ))
+        }
+    };
+    if entries.is_empty() {
+        return Err(Error::Usage("no solutions match".to_string()));
+    }
+    let source = match flag_value(args, "--example") {
+        Some(_) => InputSource::Example(parse_flag(args, "--example")?),
+        None => InputSource::Default,
+    };
+    let format = parse_format(args)?;
+    let jobs = parse_jobs(args)?;
+
+    match format {
+        Format::Text => println!(
+            "{:<4} {:>3}  {:<20} {:>12}  {:<20} {:>12}",
+            "Year", "Day", "Part 1", "Time", "Part 2", "Time"
+        ),
+        Format::Csv => println!("{}", output::CSV_HEADER),
+        Format::Json => {}
+    }
+    let start = Instant::now();
+    let results = runner::parallel(&entries, jobs, |entry| {
+        let path = source
+            .path(entry.year, entry.day)
+            .expect("run only reads input files");
+        match fs::read_to_string(&path) {
+            Ok(input) => {
+                let mut report = runner::run(entry, &input);
+                if format != Format::Text {
+                    runner::time_parse(entry, &input, &mut report);
+                }
+                Ok(report)
+            }
+            Err(_) => Err(path),
+        }
+    });
+    let wall = start.elapsed();
+
+    // Wall-clock times overlap under --jobs, so they're only summed where CPU time can't be measured
+    let mut total = Duration::ZERO;
+    let mut cpu = Some(Duration::ZERO);
+    let mut failed = false;
+    let mut reports = Vec::new();
+    for (entry, result) in entries.iter().zip(results) {
+        let report = match result {
+            Ok(report) => report,
+            Err(path) => {
+                // Keep machine-readable output parseable by reporting this on stderr
+                let missing = format!(
+                    "{:<4} {:>3}  missing input {}",
+                    entry.year,
+                    entry.day,
+                    path.display()
+                );
+                match format {
+                    Format::Text => println!("{}", missing),
+                    _ => eprintln!("{}", missing),
+                }
+                continue;
+            }
+        };
+        total += report.elapsed();
+        cpu = cpu.zip(report.cpu()).map(|(sum, day)| sum + day);
+        failed |= report.failed();
+        match format {
+            Format::Text => print_row(&report),
+            Format::Csv => print!("{}", output::to_csv(&report)),
+            Format::Json => reports.push(report),
+        }
+    }
+    let (label, total) = match cpu {
+        Some(cpu) => ("Sum of per-day CPU time", cpu),
+        None => ("Sum of per-day wall time (CPU time unavailable)", total),
+    };
+    match format {
+        Format::Text => {
+            println!("{:<65} {:>12}", label, format!("{:.2?}", total));
+            println!(
+                "{:<65} {:>12}",
+                format!("Wall time (--jobs {})", jobs),
+                format!("{:.2?}", wall)
+            );
+        }
+        Format::Csv => {}
+        Format::Json => print!("{}", output::to_json(&reports)),
+    }
+    if format != Format::Text {
+        eprintln!(
+            "{} {:.2?}, wall time {:.2?} (--jobs {})",
+            label, total, wall, jobs
+        );
+    }
+    match failed {
+        true => Err(Error::Usage("Some solutions failed".to_string())),
+        false => Ok(()),
+    }
+}
+
+fn print_row(report: &Report) {
+    let mut row = format!("{:<4} {:>3}", report.year, report.day);
+    for part in &report.parts {
+        let _ = write!(
+            row,
+            "  {:<20} {:>12}",
+            cell(&part.answer),
+            format!("{:.2?}", part.elapsed)
+        );
+    }
+    println!("{}", row);
+    for part in &report.parts {
+        if let Some(usage) = part.memory {
+            println!("{:>10}part {}: {}", "", part.part, describe_usage(usage));
+        }
+    }
+}
+
+fn describe_usage(usage: Usage) -> String {
+    format!(
+        "peak {}, {} in {} allocations",
+        memory::format_bytes(usage.peak),
+        memory::format_bytes(usage.bytes),
+        usage.allocations
+    )
+}
+
+fn cell(answer: &Result<Answer>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string().replace('\n', " "),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+fn verify(args: &[String]) -> Result<()> {
+    let entries = solution::all();
+    let results = runner::parallel(entries, parse_jobs(args)?, verify::verify);
+    let mut failed = false;
+    let mut skipped = 0;
+    for (entry, result) in entries.iter().zip(results) {
+        let status = match result {
+            Ok(Verification::Passed) => "ok".to_string(),
+            Ok(Verification::MissingInput) => {
+                skipped += 1;
+                "skipped (no input)".to_string()
+            }
+            Ok(Verification::MissingAnswers) => {
+                skipped += 1;
+                "skipped (no answers)".to_string()
+            }
+            Ok(Verification::Failed(mismatches)) => {
+                failed = true;
+                mismatches
+                    .iter()
+                    .map(|m| {
+                        format!(
+                            "FAILED part {}: expected {}, got {}",
+                            m.part, m.expected, m.actual
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            }
+            Err(e) => {
+                failed = true;
+                format!("FAILED to read: {}", e)
+            }
+        };
+        println!("{} {:>2}: {}", entry.year, entry.day, status);
+    }
+    if skipped > 0 {
+        eprintln!(
+            "warning: {} of {} days were not verified",
+            skipped,
+            entries.len()
+        );
+    }
+    match failed {
+        true => Err(Error::Usage("Some answers did not match".to_string())),
+        false => Ok(()),
+    }
+}
+
+fn bench(args: &[String]) -> Result<()> {
+    if args.len() < 2 {
+        return Err(Error::Usage(
+            "Usage: bench <YEAR> <DAY> [--part <PART>] [--iterations <N>] [--save <FILE>] [--baseline <FILE>] [--input <FILE> | --example <N>]"
+                .to_string(),
+        ));
+    }
+    let entry = find_entry(args.first(), args.get(1))?;
+    let parts = parse_parts(args)?;
+    let iterations = match flag_value(args, "--iterations") {
+        Some(_) => parse_flag(args, "--iterations")?,
+        None => 10,
+    };
+    let baseline = match flag_value(args, "--baseline") {
+        Some(path) => Some(bench::from_json(&fs::read_to_string(path)?)?),
+        None => None,
+    };
+    let input = runner::read_input(entry.year, entry.day, &input_source(args)?)?;
+
+    let measurements = parts
+        .into_iter()
+        .map(|part| bench::bench(entry, part, &input, iterations))
+        .collect::<Result<Vec<_>>>()
+        .map_err(|e| e.in_day(entry.year, entry.day))?;
+    for measurement in &measurements {
+        print_measurement(measurement);
+        let previous = baseline.iter().flatten().find(|b| {
+            (b.year, b.day, b.part) == (measurement.year, measurement.day, measurement.part)
+        });
+        if let Some(previous) = previous {
+            println!(
+                "  vs baseline: {:.2?} -> {:.2?} ({})",
+                previous.total.median,
+                measurement.total.median,
+                bench::delta_percent(previous, measurement)
+                    .map_or("n/a".to_string(), |delta| format!("{:+.1}%", delta))
+            );
         }
     }
+    if let Some(path) = flag_value(args, "--save") {
+        fs::write(path, bench::to_json(&measurements))?;
+    }
+    Ok(())
+}
+
+fn print_measurement(m: &Measurement) {
+    println!(
+        "{} day {} part {} ({} iterations)",
+        m.year, m.day, m.part, m.iterations
+    );
+    println!(
+        "  total: min {:.2?}, median {:.2?}, mean {:.2?}, stddev {:.2?}",
+        m.total.min, m.total.median, m.total.mean, m.total.stddev
+    );
+    if let Some(parse) = &m.parse {
+        println!(
+            "  parse: min {:.2?}, median {:.2?}, mean {:.2?}, stddev {:.2?}",
+            parse.min, parse.median, parse.mean, parse.stddev
+        );
+        println!("  solve: median {:.2?}", m.solve_median());
+    }
+}
+
+/// `--input <FILE>`, `--input -` or a bare `-` for stdin, `--example <N>`, or the default input file.
+fn input_source(args: &[String]) -> Result<InputSource> {
+    let input = flag_value(args, "--input");
+    let example = flag_value(args, "--example");
+    match (input, example) {
+        (Some(_), Some(_)) => Err(Error::Usage(
+            "--input and --example can't be combined".to_string(),
+        )),
+        (Some("-"), None) => Ok(InputSource::Stdin),
+        (Some(path), None) => Ok(InputSource::Path(PathBuf::from(path))),
+        (None, Some(_)) => Ok(InputSource::Example(parse_flag(args, "--example")?)),
+        (None, None) if args.iter().any(|arg| arg == "-") => Ok(InputSource::Stdin),
+        (None, None) => Ok(InputSource::Default),
+    }
+}
+
+fn new(args: &[String]) -> Result<()> {
+    let year = parse_arg(args.first(), "year")?;
+    let day = parse_arg(args.get(1), "day")?;
+    for path in scaffold::scaffold(Path::new("."), year, day)? {
+        println!(
+            "Wrote {}",
+            path.strip_prefix(".").unwrap_or(&path).display()
+        );
+    }
+    Ok(())
+}
+
+fn fetch(args: &[String]) -> Result<()> {
+    let year = parse_arg(args.first(), "year")?;
+    let day = parse_arg(args.get(1), "day")?;
+    let path = runner::input_path(year, day);
+    // Checked before building the client, so cached inputs don't need a session token
+    if fetch::is_cached(&path) {
+        println!("{} already exists", path.display());
+        return Ok(());
+    }
+    fetch::fetch(&Client::from_env()?, year, day, &path)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+fn submit(args: &[String]) -> Result<()> {
+    let entry = find_entry(args.first(), args.get(1))?;
+    let part = match args.get(2).map(String::as_str) {
+        Some("1") => 1,
+        Some("2") => 2,
+        _ => return Err(Error::Usage("Usage: submit <YEAR> <DAY> <1|2>".to_string())),
+    };
+    let input = runner::read_input(entry.year, entry.day, &InputSource::Default)?;
+    let answer = runner::catch_panic(|| entry.solution.part(part, &input, &[]))
+        .map_err(|e| e.in_day(entry.year, entry.day))?;
+    let mut history = History::load(&submit::history_path(entry.year, entry.day))?;
+    println!(
+        "Submitting {} for {} day {} part {}",
+        answer, entry.year, entry.day, part
+    );
+    let verdict = submit::submit(
+        &Client::from_env()?,
+        entry.year,
+        entry.day,
+        part,
+        &answer,
+        &mut history,
+    )?;
+    match verdict {
+        Verdict::Correct => println!("Correct!"),
+        Verdict::TooHigh => println!("Wrong, the answer is too high"),
+        Verdict::TooLow => println!("Wrong, the answer is too low"),
+        Verdict::Wrong => println!("Wrong"),
+        Verdict::Wait(seconds) => println!("Submitted too recently, wait {}s", seconds),
+        Verdict::AlreadySolved => println!("Already solved"),
+    }
+    Ok(())
+}
+
+fn watch(args: &[String]) -> Result<()> {
+    let entry = find_entry(args.first(), args.get(1))?;
+    let (year, day) = (entry.year, entry.day);
+    let input = input_source(args)?.path(year, day).ok_or_else(|| {
+        Error::Usage(
+            "Usage: watch <YEAR> <DAY> [--input <FILE> | --example <N>] [--part <PART>] [--param <KEY=VALUE>]... [--interval <MS>]"
+                .to_string(),
+        )
+    })?;
+    let interval = match flag_value(args, "--interval") {
+        Some(_) => Duration::from_millis(parse_flag(args, "--interval")?),
+        None => Duration::from_millis(500),
+    };
+    // Everything but the flags owned by watch is passed on to the solve command
+    let mut forwarded = args[..2].to_vec();
+    let mut rest = args[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--interval" | "--format" => {
+                rest.next();
+            }
+            _ => forwarded.push(arg.clone()),
+        }
+    }
+    forwarded.extend(["--format".to_string(), "csv".to_string()]);
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let source = PathBuf::from(format!("src/y{}/day{}.rs", year, day));
+
+    let mut watcher = Watcher::new(vec![source, input]);
+    let mut previous = Vec::new();
+    loop {
+        let changed = watcher
+            .changed()
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        if !changed.is_empty() {
+            println!("== {} changed", changed.join(", "));
+            let start = Instant::now();
+            let tests = Command::new(&cargo)
+                .args(["test", "--lib", "-q", &format!("y{}::day{}::", year, day)])
+                .status()?;
+            println!(
+                "Tests {}",
+                match tests.success() {
+                    true => "passed",
+                    false => "FAILED",
+                }
+            );
+            let output = Command::new(&cargo)
+                .args(["run", "--release", "-q", "--"])
+                .args(&forwarded)
+                .stderr(Stdio::inherit())
+                .output()?;
+            match watch::parse_csv(&String::from_utf8_lossy(&output.stdout)) {
+                Ok(current) => {
+                    for line in watch::diff(&previous, &current) {
+                        println!("{}", line);
+                    }
+                    previous = current;
+                }
+                Err(_) => println!("Solution did not run"),
+            }
+            println!("Done in {:.2?}, watching for changes", start.elapsed());
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn parse_parts(args: &[String]) -> Result<Vec<u8>> {
+    match flag_value(args, "--part") {
+        Some("1") => Ok(vec![1]),
+        Some("2") => Ok(vec![2]),
+        Some(part) => Err(Error::Usage(format!(
+            "Invalid part: {} (expected 1 or 2)",
+            part
+        ))),
+        None => Ok(vec![1, 2]),
+    }
+}
+
+/// `--jobs <N>` worker threads, defaulting to one per available core.
+fn parse_jobs(args: &[String]) -> Result<usize> {
+    match flag_value(args, "--jobs") {
+        Some("0") => Err(Error::Usage("--jobs must be at least 1".to_string())),
+        Some(_) => parse_flag(args, "--jobs"),
+        None => Ok(runner::default_threads()),
+    }
+}
+
+fn parse_format(args: &[String]) -> Result<Format> {
+    match flag_value(args, "--format") {
+        Some(_) => parse_flag(args, "--format"),
+        None => Ok(Format::Text),
+    }
+}
+
+fn find_entry(year: Option<&String>, day: Option<&String>) -> Result<&'static Entry> {
+    let year = parse_arg(year, "year")?;
+    let day = parse_arg(day, "day")?;
+    solution::find(year, day)
+        .ok_or_else(|| Error::Usage(format!("Solution does not exist for {} {}", year, day)))
+}
+
+fn parse_arg<T: FromStr>(value: Option<&String>, name: &str) -> Result<T> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| Error::Usage(format!("Invalid {}: {}", name, value))),
+        None => Err(Error::Usage(format!("Missing {}!", name))),
+    }
+}
+
+fn parse_flag<T: FromStr>(args: &[String], flag: &str) -> Result<T> {
+    let value = args
+        .iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1));
+    parse_arg(value, flag.trim_start_matches('-'))
+}
+
+fn flag_values<'a>(args: &'a [String], flag: &'a str) -> impl Iterator<Item = &'a str> {
+    args.windows(2)
+        .filter(move |pair| pair[0] == flag)
+        .map(|pair| pair[1].as_str())
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
 }
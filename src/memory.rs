@@ -0,0 +1,175 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Wraps the system allocator, counting the heap use of the allocating thread.
+/// Installed as the global allocator by the `memory` feature; otherwise nothing is counted.
+pub struct Counting;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    /// Bytes currently allocated, which goes negative when freeing memory allocated by another thread
+    current: isize,
+    peak: isize,
+    bytes: usize,
+    allocations: usize,
+}
+
+thread_local! {
+    // Counted per thread, so days run in parallel don't see each other's allocations
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            current: 0,
+            peak: 0,
+            bytes: 0,
+            allocations: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    // Fails while the thread is being torn down, at which point nobody reads the counters anymore
+    let _ = COUNTERS.try_with(|counters| {
+        let mut value = counters.get();
+        f(&mut value);
+        counters.set(value);
+    });
+}
+
+fn allocated(size: usize) {
+    update(|counters| {
+        counters.current += size as isize;
+        counters.peak = counters.peak.max(counters.current);
+        counters.bytes += size;
+        counters.allocations += 1;
+    });
+}
+
+fn freed(size: usize) {
+    update(|counters| counters.current -= size as isize);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Heap use of a measured closure.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Usage {
+    /// Most bytes allocated at once, on top of what was allocated before
+    pub peak: usize,
+    /// Bytes allocated in total, counting a reallocation as a new allocation
+    pub bytes: usize,
+    pub allocations: usize,
+}
+
+/// Whether the counting allocator is installed, i.e. [`measure`] reports anything but zeroes.
+pub fn enabled() -> bool {
+    cfg!(feature = "memory")
+}
+
+/// Runs `f`, counting the heap use of the current thread while it runs.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let before = COUNTERS.with(|counters| {
+        let before = counters.get();
+        counters.set(Counters {
+            peak: before.current,
+            ..before
+        });
+        before
+    });
+    let result = f();
+    let after = COUNTERS.with(|counters| {
+        let after = counters.get();
+        // Keep the peak of an enclosing measurement
+        counters.set(Counters {
+            peak: after.peak.max(before.peak),
+            ..after
+        });
+        after
+    });
+    let usage = Usage {
+        peak: (after.peak - before.current).max(0) as usize,
+        bytes: after.bytes - before.bytes,
+        allocations: after.allocations - before.allocations,
+    };
+    (result, usage)
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.50 KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let (sum, usage) = measure(|| {
+            let (_, inner) = measure(|| vec![0u8; 4096]);
+            let kept = vec![1u64; 1024];
+            assert_eq!(inner.bytes, 4096 * enabled() as usize);
+            kept.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 1024);
+        match enabled() {
+            true => {
+                assert_eq!(usage.allocations, 2);
+                assert_eq!(usage.bytes, 4096 + 8192);
+                assert_eq!(usage.peak, 8192);
+            }
+            false => assert_eq!(usage, Usage::default()),
+        }
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.00 MiB");
+    }
+}
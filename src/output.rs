@@ -0,0 +1,213 @@
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::memory::Usage;
+use crate::runner::Report;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(Error::Usage(format!(
+                "Invalid format: {} (expected json, csv or text)",
+                s
+            ))),
+        }
+    }
+}
+
+/// The memory columns are empty unless built with the `memory` feature.
+pub const CSV_HEADER: &str =
+    "year,day,part,status,answer,error,parse_ns,solve_ns,peak_bytes,alloc_bytes,allocations";
+
+/// One line per part, in the column order of [`CSV_HEADER`].
+pub fn to_csv(report: &Report) -> String {
+    let mut csv = String::new();
+    for result in &report.parts {
+        let (status, answer, error) = match &result.answer {
+            Ok(answer) => ("ok", answer.to_string(), String::new()),
+            Err(e) => ("error", String::new(), e.to_string()),
+        };
+        let memory = match result.memory {
+            Some(usage) => format!("{},{},{}", usage.peak, usage.bytes, usage.allocations),
+            None => ",,".to_string(),
+        };
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{}",
+            report.year,
+            report.day,
+            result.part,
+            status,
+            csv_field(&answer),
+            csv_field(&error),
+            result.parse.map_or(String::new(), |d| nanos(d).to_string()),
+            nanos(result.solve()),
+            memory
+        );
+    }
+    csv
+}
+
+/// A JSON array with one object per part of every report.
+pub fn to_json(reports: &[Report]) -> String {
+    let rows = reports
+        .iter()
+        .flat_map(|report| {
+            report.parts.iter().map(|result| {
+                let (status, answer, error) = match &result.answer {
+                    Ok(answer) => ("ok", json_answer(answer), "null".to_string()),
+                    Err(e) => ("error", "null".to_string(), json_string(&e.to_string())),
+                };
+                format!(
+                    "  {{\"year\": {}, \"day\": {}, \"part\": {}, \"status\": \"{}\", \"answer\": {}, \"error\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"memory\": {}}}",
+                    report.year,
+                    report.day,
+                    result.part,
+                    status,
+                    answer,
+                    error,
+                    result.parse.map_or("null".to_string(), |d| nanos(d).to_string()),
+                    nanos(result.solve()),
+                    result.memory.map_or("null".to_string(), json_memory)
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    match rows.is_empty() {
+        true => "[]\n".to_string(),
+        false => format!("[\n{}\n]\n", rows.join(",\n")),
+    }
+}
+
+fn nanos(duration: Duration) -> u128 {
+    duration.as_nanos()
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(number) => number.to_string(),
+        Answer::Unsolved => "null".to_string(),
+        answer => json_string(&answer.to_string()),
+    }
+}
+
+fn json_memory(usage: Usage) -> String {
+    format!(
+        "{{\"peak_bytes\": {}, \"alloc_bytes\": {}, \"allocations\": {}}}",
+        usage.peak, usage.bytes, usage.allocations
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::PartReport;
+
+    fn report() -> Report {
+        Report {
+            year: 2024,
+            day: 18,
+            parts: vec![
+                PartReport {
+                    part: 1,
+                    answer: Ok(Answer::Number(22)),
+                    elapsed: Duration::from_nanos(300),
+                    cpu: None,
+                    parse: Some(Duration::from_nanos(100)),
+                    memory: Some(Usage {
+                        peak: 1024,
+                        bytes: 4096,
+                        allocations: 3,
+                    }),
+                },
+                PartReport {
+                    part: 2,
+                    answer: Ok(Answer::Text("6,1".to_string())),
+                    elapsed: Duration::from_nanos(500),
+                    cpu: None,
+                    parse: Some(Duration::from_nanos(100)),
+                    memory: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        assert_eq!("text".parse::<Format>().unwrap(), Format::Text);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&report()),
+            "2024,18,1,ok,22,,100,200,1024,4096,3\n2024,18,2,ok,\"6,1\",,100,400,,,\n"
+        );
+        let mut report = report();
+        report.parts[0].parse = None;
+        assert!(to_csv(&report).starts_with("2024,18,1,ok,22,,,300,1024,4096,3\n"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut report = report();
+        report.parts[1].answer = Err(Error::parse("missing \"comma\"").at_line(3));
+        report.parts[1].parse = None;
+        assert_eq!(
+            to_json(&[report]),
+            "[\n  {\"year\": 2024, \"day\": 18, \"part\": 1, \"status\": \"ok\", \"answer\": 22, \"error\": null, \"parse_ns\": 100, \"solve_ns\": 200, \"memory\": {\"peak_bytes\": 1024, \"alloc_bytes\": 4096, \"allocations\": 3}},\n  {\"year\": 2024, \"day\": 18, \"part\": 2, \"status\": \"error\", \"answer\": null, \"error\": \"failed to parse line 3: missing \\\"comma\\\"\", \"parse_ns\": null, \"solve_ns\": 500, \"memory\": null}\n]\n"
+        );
+        assert_eq!(to_json(&[]), "[]\n");
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+}
@@ -0,0 +1,164 @@
+use crate::error::{Error, Result};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A `key=value` parameter override, as passed with `--param`.
+pub type Override = (String, String);
+
+pub fn parse_override(s: &str) -> Result<Override> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(Error::Usage(format!(
+            "Invalid parameter: {} (expected key=value)",
+            s
+        ))),
+    }
+}
+
+/// A day's parameters, defaulting to the values for the real input. Declared with [`crate::params!`].
+pub trait Params: Default {
+    const NAMES: &'static [&'static str];
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+
+    fn from_overrides(overrides: &[Override]) -> Result<Self> {
+        let mut params = Self::default();
+        for (key, value) in overrides {
+            params.set(key, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Days without parameters reject every override.
+impl Params for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, key: &str, _value: &str) -> Result<()> {
+        Err(unknown(key, Self::NAMES))
+    }
+}
+
+pub fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| Error::Usage(format!("Invalid value for parameter {}: {}", key, value)))
+}
+
+/// Checks a parameter read by a part against its smallest sensible value.
+pub fn at_least<T: PartialOrd + Display>(key: &str, value: T, min: T) -> Result<T> {
+    match value >= min {
+        true => Ok(value),
+        false => Err(Error::Usage(format!(
+            "Invalid value for parameter {}: {} (must be at least {})",
+            key, value, min
+        ))),
+    }
+}
+
+pub fn unknown(key: &str, names: &[&str]) -> Error {
+    match names.is_empty() {
+        true => Error::Usage(format!(
+            "Unknown parameter {}, this day takes no parameters",
+            key
+        )),
+        false => Error::Usage(format!(
+            "Unknown parameter {}, expected one of: {}",
+            key,
+            names.join(", ")
+        )),
+    }
+}
+
+/// Declares a day's parameter struct with a default per field, e.g.
+/// `params!(struct Params { size: usize = 71, sim_len: usize = 1024 });`
+#[macro_export]
+macro_rules! params {
+    (struct $name:ident { $($field:ident: $ty:ty = $default:expr),* $(,)? }) => {
+        #[derive(Clone, Debug, PartialEq)]
+        struct $name {
+            $($field: $ty),*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default),*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn set(&mut self, key: &str, value: &str) -> $crate::error::Result<()> {
+                match key {
+                    $(stringify!($field) => self.$field = $crate::params::parse_value(key, value)?,)*
+                    _ => return Err($crate::params::unknown(key, Self::NAMES)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params!(
+        struct Example { size: usize = 71, name: String = "a".to_string() }
+    );
+
+    fn overrides(pairs: &[(&str, &str)]) -> Vec<Override> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(
+            parse_override("size=7").unwrap(),
+            ("size".to_string(), "7".to_string())
+        );
+        assert!(parse_override("size").is_err());
+        assert!(parse_override("=7").is_err());
+    }
+
+    #[test]
+    fn test_from_overrides() {
+        assert_eq!(
+            Example::from_overrides(&[]).unwrap(),
+            Example {
+                size: 71,
+                name: "a".to_string()
+            }
+        );
+        let example = Example::from_overrides(&overrides(&[("size", "7")])).unwrap();
+        assert_eq!(example.size, 7);
+        assert_eq!(
+            Example::from_overrides(&overrides(&[("size", "x")]))
+                .unwrap_err()
+                .to_string(),
+            "Invalid value for parameter size: x"
+        );
+        assert_eq!(
+            Example::from_overrides(&overrides(&[("len", "7")]))
+                .unwrap_err()
+                .to_string(),
+            "Unknown parameter len, expected one of: size, name"
+        );
+        assert!(<()>::from_overrides(&overrides(&[("size", "7")])).is_err());
+    }
+
+    #[test]
+    fn test_at_least() {
+        assert_eq!(at_least("size", 7, 1).unwrap(), 7);
+        assert_eq!(
+            at_least("size", 0, 1).unwrap_err().to_string(),
+            "Invalid value for parameter size: 0 (must be at least 1)"
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use crate::answer::Answer;
+use crate::cpu;
+use crate::error::{Error, Result};
+use crate::memory::{self, Usage};
+use crate::params::Override;
+use crate::solution::Entry;
+use std::cell::Cell;
+use std::fs;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Workers get the main thread's usual stack size, so deeply recursive solutions behave the same.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+thread_local! {
+    /// Whether this thread is inside [`catch_panic`], whose panics are reported as errors instead
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub elapsed: Duration,
+    /// CPU time the part's thread spent in it, where the platform can measure it
+    pub cpu: Option<Duration>,
+    /// Time spent in the day's parser alone, if it has one and [`time_parse`] was called
+    pub parse: Option<Duration>,
+    /// Heap use of the part, if built with the `memory` feature
+    pub memory: Option<Usage>,
+}
+
+impl PartReport {
+    /// Time spent solving, i.e. the part's time without the parser's time.
+    pub fn solve(&self) -> Duration {
+        match self.parse {
+            Some(parse) => self.elapsed.saturating_sub(parse),
+            None => self.elapsed,
+        }
+    }
+}
+
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    /// The parts that were run, in order
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    pub fn elapsed(&self) -> Duration {
+        self.parts.iter().map(|part| part.elapsed).sum()
+    }
+
+    /// CPU time spent in all parts, or `None` if it couldn't be measured.
+    pub fn cpu(&self) -> Option<Duration> {
+        self.parts.iter().map(|part| part.cpu).sum()
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartReport> {
+        self.parts.iter().find(|report| report.part == part)
+    }
+
+    pub fn failed(&self) -> bool {
+        self.parts.iter().any(|part| part.answer.is_err())
+    }
+}
+
+pub fn input_path(year: u16, day: u8) -> PathBuf {
+    PathBuf::from("inputs")
+        .join(year.to_string())
+        .join(format!("{}.in", day))
+}
+
+pub fn example_path(year: u16, day: u8, example: u32) -> PathBuf {
+    input_path(year, day).with_extension(format!("example{}.in", example))
+}
+
+/// Where a day's input is read from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum InputSource {
+    /// `inputs/<YEAR>/<DAY>.in`
+    #[default]
+    Default,
+    /// `inputs/<YEAR>/<DAY>.example<N>.in`
+    Example(u32),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Default => Some(input_path(year, day)),
+            InputSource::Example(example) => Some(example_path(year, day, *example)),
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+}
+
+pub fn read_input(year: u16, day: u8, source: &InputSource) -> Result<String> {
+    let input = match source.path(year, day) {
+        Some(path) => fs::read_to_string(&path).map_err(|source| Error::Input { path, source })?,
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+    match input.is_empty() {
+        true => Err(Error::parse("input is empty")),
+        false => Ok(input),
+    }
+}
+
+pub fn run(entry: &Entry, input: &str) -> Report {
+    run_with(entry, input, &[1, 2], &[])
+}
+
+/// Runs the given `parts` of a day, with its parameters overridden by `params`.
+pub fn run_with(entry: &Entry, input: &str, parts: &[u8], params: &[Override]) -> Report {
+    let parts = parts
+        .iter()
+        .map(|&part| run_part(part, || entry.solution.part(part, input, params)))
+        .collect::<Vec<_>>();
+    Report {
+        year: entry.year,
+        day: entry.day,
+        parts,
+    }
+}
+
+/// Runs the day's parser once more to split the parts' times into parse and solve time. Call it
+/// after running the parts, so the parser runs as warm as it did inside them.
+pub fn time_parse(entry: &Entry, input: &str, report: &mut Report) {
+    let start = Instant::now();
+    let parse = match catch_panic(|| Ok(entry.solution.parse(input))) {
+        Ok(true) => Some(start.elapsed()),
+        _ => None,
+    };
+    for part in report.parts.iter_mut() {
+        part.parse = parse;
+    }
+}
+
+fn run_part(part: u8, solve: impl FnOnce() -> Result<Answer>) -> PartReport {
+    let start = Instant::now();
+    let ((answer, usage), cpu) = cpu::measure(|| memory::measure(|| catch_panic(solve)));
+    let elapsed = start.elapsed();
+    PartReport {
+        part,
+        answer,
+        elapsed,
+        cpu,
+        parse: None,
+        memory: memory::enabled().then_some(usage),
+    }
+}
+
+/// Runs `f`, turning a panic into [`Error::Panic`] so one broken day can't take down a whole run. Such
+/// panics aren't printed, while panics anywhere else still reach the previously installed hook.
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|catching| catching.set(was_catching));
+    result.unwrap_or_else(|payload| {
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(message), _) => message.to_string(),
+            (_, Some(message)) => message.clone(),
+            _ => "unknown panic".to_string(),
+        };
+        Err(Error::Panic(message))
+    })
+}
+
+/// Maps `f` over `items` on up to `threads` worker threads, returning the results in the order of `items`.
+/// Workers take the next unprocessed item as they finish, so one slow day doesn't hold up a whole batch.
+pub fn parallel<T: Sync, R: Send>(
+    items: &[T],
+    threads: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(items.iter().map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            thread::Builder::new()
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let result = f(item);
+                    results.lock().unwrap()[index] = Some(result);
+                })
+                .expect("failed to spawn worker thread");
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item was processed"))
+        .collect()
+}
+
+/// The number of worker threads to use when none is asked for.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(input_path(2024, 7), PathBuf::from("inputs/2024/7.in"));
+    }
+
+    #[test]
+    fn test_example_path() {
+        assert_eq!(
+            example_path(2024, 7, 1),
+            PathBuf::from("inputs/2024/7.example1.in")
+        );
+        assert_eq!(
+            InputSource::Example(2).path(2023, 19),
+            Some(PathBuf::from("inputs/2023/19.example2.in"))
+        );
+        assert_eq!(InputSource::Stdin.path(2023, 19), None);
+    }
+
+    #[test]
+    fn test_read_input() {
+        let path = std::env::temp_dir().join("aoc-rust-read-input.in");
+        fs::write(&path, "()())").unwrap();
+        let source = InputSource::Path(path.clone());
+        assert_eq!(read_input(2015, 1, &source).unwrap(), "()())");
+        fs::write(&path, "").unwrap();
+        assert!(read_input(2015, 1, &source).is_err());
+        fs::remove_file(&path).unwrap();
+        let error = read_input(2015, 1, &source).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with(&format!("failed to read input {}", path.display())));
+    }
+
+    #[test]
+    fn test_run() {
+        let report = run(solution::find(2015, 1).unwrap(), "()())");
+        assert_eq!((report.year, report.day), (2015, 1));
+        let part1 = report.part(1).unwrap();
+        let part2 = report.part(2).unwrap();
+        assert_eq!(part1.answer.as_ref().unwrap(), &Answer::Number(-1));
+        assert_eq!(part2.answer.as_ref().unwrap(), &Answer::Number(5));
+        assert_eq!(report.elapsed(), part1.elapsed + part2.elapsed);
+        assert_eq!(report.cpu().is_some(), cfg!(target_os = "linux"));
+        assert!(part1.parse.is_none());
+        assert_eq!(part1.solve(), part1.elapsed);
+        assert!(!report.failed());
+    }
+
+    #[test]
+    fn test_run_with() {
+        let entry = solution::find(2024, 18).unwrap();
+        let input = "5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1";
+        let params = vec![
+            ("size".to_string(), "7".to_string()),
+            ("sim_len".to_string(), "12".to_string()),
+        ];
+        let report = run_with(entry, input, &[1], &params);
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(
+            report.part(1).unwrap().answer.as_ref().unwrap(),
+            &Answer::Number(22)
+        );
+        let report = run_with(entry, input, &[2], &[("len".to_string(), "7".to_string())]);
+        assert!(report.failed());
+    }
+
+    #[test]
+    fn test_time_parse() {
+        let (entry, input) = (solution::find(2024, 1).unwrap(), "3   4\n4   3\n2   5");
+        let mut report = run(entry, input);
+        assert!(report.parts.iter().all(|part| part.parse.is_none()));
+        time_parse(entry, input, &mut report);
+        let part1 = report.part(1).unwrap();
+        let parse = part1.parse.unwrap();
+        assert_eq!(part1.solve(), part1.elapsed.saturating_sub(parse));
+        assert_eq!(report.parts[1].part, 2);
+    }
+
+    #[test]
+    fn test_parallel() {
+        let items = (0..100).collect::<Vec<u64>>();
+        let squares = items.iter().map(|i| i * i).collect::<Vec<_>>();
+        assert_eq!(parallel(&items, 4, |i| i * i), squares);
+        assert_eq!(parallel(&items, 1, |i| i * i), squares);
+        assert_eq!(parallel(&items, 0, |i| i * i), squares);
+        assert!(parallel(&[] as &[u64], 4, |i| i * i).is_empty());
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| Ok(1)).unwrap(), 1);
+        let error = catch_panic::<()>(|| panic!("boom")).unwrap_err();
+        assert_eq!(error.to_string(), "solution panicked: boom");
+        assert!(!CATCHING.with(Cell::get));
+    }
+}
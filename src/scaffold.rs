@@ -0,0 +1,159 @@
+use crate::error::{Error, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = "crate::solution!(part1, part2);
+
+fn part1(_input: &str) -> Option<usize> {
+    None
+}
+
+fn part2(_input: &str) -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = \"\";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(INPUT), None);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(INPUT), None);
+    }
+}
+";
+
+/// Creates `src/y<YEAR>/day<DAY>.rs` with its `mod` declarations and an empty input under `root`.
+/// build.rs registers the new day on the next build. Returns the files that were created or changed.
+pub fn scaffold(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Usage(format!(
+            "Invalid day: {} (expected 1-25)",
+            day
+        )));
+    }
+    let year_dir = root.join("src").join(format!("y{}", year));
+    let day_path = year_dir.join(format!("day{}.rs", day));
+    if day_path.exists() {
+        return Err(Error::Usage(format!(
+            "{} already exists",
+            day_path.display()
+        )));
+    }
+
+    let mut changed = Vec::new();
+    let year_mod = year_dir.join("mod.rs");
+    if !year_mod.exists() {
+        fs::create_dir_all(&year_dir)?;
+        let lib = root.join("src").join("lib.rs");
+        add_mod(&lib, &format!("y{}", year))?;
+        changed.push(lib);
+    }
+    fs::write(&day_path, TEMPLATE)?;
+    changed.push(day_path);
+    add_mod(&year_mod, &format!("day{}", day))?;
+    changed.push(year_mod);
+
+    let input = root
+        .join("inputs")
+        .join(year.to_string())
+        .join(format!("{}.in", day));
+    if !input.exists() {
+        fs::create_dir_all(input.parent().expect("input path has a parent"))?;
+        fs::write(&input, "")?;
+        changed.push(input);
+    }
+    Ok(changed)
+}
+
+/// Adds `pub mod <name>;` to `path`, keeping the declarations in alphabetical order.
+fn add_mod(path: &Path, name: &str) -> Result<()> {
+    let declaration = format!("pub mod {};", name);
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let mut lines = content.lines().collect::<Vec<_>>();
+    if lines.contains(&declaration.as_str()) {
+        return Ok(());
+    }
+    let index = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod ") && *line > declaration.as_str())
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| line.starts_with("pub mod "))
+                .map(|i| i + 1)
+        })
+        .unwrap_or(lines.len());
+    lines.insert(index, &declaration);
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-rust-scaffold-{}", name));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/y2024")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod util;\npub mod y2024;\n").unwrap();
+        fs::write(
+            root.join("src/y2024/mod.rs"),
+            "pub mod day1;\npub mod day3;\n",
+        )
+        .unwrap();
+        root
+    }
+
+    #[test]
+    fn test_scaffold_day() {
+        let root = temp_root("day");
+        let changed = scaffold(&root, 2024, 25).unwrap();
+        assert_eq!(
+            changed,
+            vec![
+                root.join("src/y2024/day25.rs"),
+                root.join("src/y2024/mod.rs"),
+                root.join("inputs/2024/25.in"),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/y2024/mod.rs")).unwrap(),
+            "pub mod day1;\npub mod day25;\npub mod day3;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/y2024/day25.rs")).unwrap(),
+            TEMPLATE
+        );
+        assert!(scaffold(&root, 2024, 25).is_err());
+        assert!(scaffold(&root, 2024, 26).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_year() {
+        let root = temp_root("year");
+        scaffold(&root, 2016, 1).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "pub mod util;\npub mod y2016;\npub mod y2024;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/y2016/mod.rs")).unwrap(),
+            "pub mod day1;\n"
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::params::Override;
+use std::collections::BTreeSet;
+
+pub trait Solution: Sync {
+    /// Runs part 1 with the day's parameters overridden by `params`.
+    fn part1_with(&self, input: &str, params: &[Override]) -> Result<Answer>;
+    fn part2_with(&self, input: &str, params: &[Override]) -> Result<Answer>;
+
+    /// Names of the parameters that can be overridden, empty if the day has none.
+    fn params(&self) -> &'static [&'static str];
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        self.part1_with(input, &[])
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        self.part2_with(input, &[])
+    }
+
+    fn part(&self, part: u8, input: &str, params: &[Override]) -> Result<Answer> {
+        match part {
+            1 => self.part1_with(input, params),
+            _ => self.part2_with(input, params),
+        }
+    }
+
+    /// Runs only the day's parser, so parse time can be measured apart from solve time.
+    /// Returns `false` if the day has no separate parse step.
+    fn parse(&self, _input: &str) -> bool {
+        false
+    }
+}
+
+/// Implements [`Solution`] for a day as `Solver`, converting each part's result into an [`Answer`].
+/// Parts may return either a plain value or a `Result` whose error converts into [`crate::error::Error`].
+/// An optional third argument names the parser shared by both parts. Days with parameters name
+/// their [`crate::params!`] struct after a `;`, e.g. `solution!(part1, part2, parse; Params)`,
+/// and both parts then take it as a second argument.
+#[macro_export]
+macro_rules! solution {
+    (@solver $params:ty, $part1:expr, $part2:expr $(, $parse:expr)?) => {
+        pub struct Solver;
+
+        impl $crate::solution::Solution for Solver {
+            fn part1_with(
+                &self,
+                input: &str,
+                params: &[$crate::params::Override],
+            ) -> $crate::error::Result<$crate::answer::Answer> {
+                let params = <$params as $crate::params::Params>::from_overrides(params)?;
+                $crate::answer::IntoAnswer::into_answer(($part1)(input, &params))
+            }
+
+            fn part2_with(
+                &self,
+                input: &str,
+                params: &[$crate::params::Override],
+            ) -> $crate::error::Result<$crate::answer::Answer> {
+                let params = <$params as $crate::params::Params>::from_overrides(params)?;
+                $crate::answer::IntoAnswer::into_answer(($part2)(input, &params))
+            }
+
+            fn params(&self) -> &'static [&'static str] {
+                <$params as $crate::params::Params>::NAMES
+            }
+            $(
+                fn parse(&self, input: &str) -> bool {
+                    let _ = std::hint::black_box(($parse)(input));
+                    true
+                }
+            )?
+        }
+    };
+    ($part1:ident, $part2:ident $(, $parse:expr)?) => {
+        $crate::solution!(@solver (), |input, _: &()| $part1(input), |input, _: &()| $part2(input) $(, $parse)?);
+    };
+    ($part1:ident, $part2:ident $(, $parse:expr)?; $params:ty) => {
+        $crate::solution!(@solver $params, $part1, $part2 $(, $parse)?);
+    };
+}
+
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub solution: &'static dyn Solution,
+}
+
+// One entry per `src/yYYYY/dayN.rs`, sorted by year and day (see build.rs)
+static SOLUTIONS: &[Entry] = include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+pub fn all() -> &'static [Entry] {
+    SOLUTIONS
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Entry> {
+    all()
+        .iter()
+        .find(|entry| entry.year == year && entry.day == day)
+}
+
+pub fn years() -> BTreeSet<u16> {
+    all().iter().map(|entry| entry.year).collect()
+}
+
+pub fn days(year: u16) -> impl Iterator<Item = &'static Entry> {
+    all().iter().filter(move |entry| entry.year == year)
+}
+
+pub fn missing(year: u16) -> Vec<u8> {
+    (1..=25).filter(|day| find(year, *day).is_none()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_sorted() {
+        let keys: Vec<_> = all().iter().map(|entry| (entry.year, entry.day)).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn test_find() {
+        assert!(find(2024, 16).is_some());
+        assert!(find(2024, 22).is_none());
+        assert!(find(1999, 1).is_none());
+    }
+
+    #[test]
+    fn test_years() {
+        assert_eq!(
+            years().into_iter().collect::<Vec<_>>(),
+            vec![2015, 2023, 2024]
+        );
+    }
+
+    #[test]
+    fn test_missing() {
+        assert_eq!(missing(2023), vec![12, 13, 17, 20, 21, 22, 23, 24, 25]);
+        assert_eq!(missing(2024), vec![22, 24, 25]);
+    }
+}
@@ -0,0 +1,405 @@
+use crate::answer::Answer;
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::runner;
+use crate::verify;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after a previous answer, retry after this many seconds
+    Wait(u64),
+    /// The part was already solved, so the server didn't check the answer
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the server's HTML response.
+    pub fn parse(body: &str) -> Option<Verdict> {
+        if body.contains("That's the right answer") {
+            return Some(Verdict::Correct);
+        }
+        if body.contains("You don't seem to be solving the right level") {
+            return Some(Verdict::AlreadySolved);
+        }
+        if body.contains("You gave an answer too recently") {
+            let wait = body
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map_or(60, |(wait, _)| parse_wait(wait));
+            return Some(Verdict::Wait(wait));
+        }
+        if body.contains("That's not the right answer") {
+            return Some(match body {
+                body if body.contains("your answer is too high") => Verdict::TooHigh,
+                body if body.contains("your answer is too low") => Verdict::TooLow,
+                _ => Verdict::Wrong,
+            });
+        }
+        None
+    }
+}
+
+/// Parses durations like `1m 30s` or `45s` into seconds.
+fn parse_wait(wait: &str) -> u64 {
+    wait.split_whitespace()
+        .filter_map(|part| match part.split_at(part.len().saturating_sub(1)) {
+            (minutes, "m") => minutes.parse::<u64>().ok().map(|m| m * 60),
+            (seconds, "s") => seconds.parse::<u64>().ok(),
+            _ => None,
+        })
+        .sum()
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "wait:{}", seconds),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            s => s
+                .strip_prefix("wait:")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Verdict::Wait)
+                .ok_or_else(|| Error::parse(format!("unknown verdict '{}'", s))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    /// Seconds since the unix epoch
+    pub time: u64,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Every submitted answer of a day, one tab-separated `time part verdict answer` line per attempt.
+pub struct History {
+    path: PathBuf,
+    pub attempts: Vec<Attempt>,
+}
+
+pub fn history_path(year: u16, day: u8) -> PathBuf {
+    runner::input_path(year, day).with_extension("history")
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let attempts = crate::error::parse_lines(&content, |line| {
+            let fields = line.splitn(4, '\t').collect::<Vec<_>>();
+            match fields[..] {
+                [time, part, verdict, answer] => Ok(Attempt {
+                    time: time.parse()?,
+                    part: part.parse()?,
+                    verdict: verdict.parse()?,
+                    answer: answer.to_string(),
+                }),
+                _ => Err(Error::parse("expected time, part, verdict and answer")),
+            }
+        })?;
+        Ok(History {
+            path: path.to_path_buf(),
+            attempts,
+        })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            attempt.time, attempt.part, attempt.verdict, attempt.answer
+        )?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Refuses answers that can't be right given earlier attempts: the part is already solved, the
+    /// same answer was rejected, a number lies outside a known too-high/too-low bound, or the server
+    /// asked to wait.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<()> {
+        let refuse = |reason: String| Err(Error::Submit(reason));
+        let number = answer.parse::<i128>().ok();
+        for attempt in self.attempts.iter().filter(|attempt| attempt.part == part) {
+            let previous = attempt.answer.parse::<i128>().ok();
+            match attempt.verdict {
+                Verdict::Correct => {
+                    return refuse(format!(
+                        "part {} was already solved with {}",
+                        part, attempt.answer
+                    ))
+                }
+                Verdict::Wait(seconds) if attempt.time + seconds > now => {
+                    return refuse(format!(
+                        "the server asked to wait another {}s",
+                        attempt.time + seconds - now
+                    ))
+                }
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong if attempt.answer == answer => {
+                    return refuse(format!(
+                        "{} was already rejected ({})",
+                        answer, attempt.verdict
+                    ))
+                }
+                Verdict::TooHigh if number.zip(previous).is_some_and(|(n, p)| n >= p) => {
+                    return refuse(format!(
+                        "{} is too high, {} already was",
+                        answer, attempt.answer
+                    ))
+                }
+                Verdict::TooLow if number.zip(previous).is_some_and(|(n, p)| n <= p) => {
+                    return refuse(format!(
+                        "{} is too low, {} already was",
+                        answer, attempt.answer
+                    ))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Submits `answer` unless the history rules it out, records the attempt, and stores correct
+/// answers in the day's `.ans` file for `verify`.
+pub fn submit(
+    client: &Client,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+    history: &mut History,
+) -> Result<Verdict> {
+    let answer = match answer {
+        Answer::Number(_) | Answer::Text(_) => answer.to_string(),
+        Answer::Lines(_) => {
+            return Err(Error::Submit(
+                "multi-line answers have to be read and submitted by hand".to_string(),
+            ))
+        }
+        Answer::Unsolved => return Err(Error::Submit("the part is unsolved".to_string())),
+    };
+    history.check(part, &answer, unix_now())?;
+
+    let part_level = part.to_string();
+    let response = client.post(
+        &format!("/{}/day/{}/answer", year, day),
+        &[("level", &part_level), ("answer", &answer)],
+    )?;
+    let verdict = Verdict::parse(&response.body)
+        .ok_or_else(|| Error::Http("unrecognized response to the submission".to_string()))?;
+    history.record(Attempt {
+        time: unix_now(),
+        part,
+        verdict,
+        answer: answer.clone(),
+    })?;
+    if verdict == Verdict::Correct {
+        record_answer(&verify::answers_path(year, day), part, &answer)?;
+    }
+    Ok(verdict)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Sets the `Part <PART>: <answer>` line of an answers file, keeping the other part.
+fn record_answer(path: &Path, part: u8, answer: &str) -> Result<()> {
+    let mut expected = match fs::read_to_string(path) {
+        Ok(content) => verify::Expected::parse(&content),
+        Err(_) => verify::Expected::default(),
+    };
+    match part {
+        1 => expected.part1 = Some(answer.to_string()),
+        _ => expected.part2 = Some(answer.to_string()),
+    }
+    let lines = [(1, expected.part1), (2, expected.part2)]
+        .into_iter()
+        .filter_map(|(part, answer)| Some(format!("Part {}: {}\n", part, answer?)))
+        .collect::<String>();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, lines)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockServer;
+    use std::env;
+
+    fn temp_history(name: &str) -> History {
+        let path = env::temp_dir().join(format!("aoc-rust-{}.history", name));
+        let _ = fs::remove_file(&path);
+        History::load(&path).unwrap()
+    }
+
+    fn attempt(time: u64, verdict: Verdict, answer: &str) -> Attempt {
+        Attempt {
+            time,
+            part: 1,
+            verdict,
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        let page = |text: &str| format!("<main><article><p>{}</p></article></main>", text);
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer. If you're stuck...")),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently. You have 1m 30s left to wait."
+            )),
+            Some(Verdict::Wait(90))
+        );
+        assert_eq!(
+            Verdict::parse(&page("You don't seem to be solving the right level.")),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(Verdict::parse("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_verdict_roundtrip() {
+        for verdict in [Verdict::Correct, Verdict::TooLow, Verdict::Wait(42)] {
+            assert_eq!(verdict.to_string().parse::<Verdict>().unwrap(), verdict);
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let mut history = temp_history("check");
+        history.attempts = vec![
+            attempt(100, Verdict::TooHigh, "500"),
+            attempt(200, Verdict::TooLow, "100"),
+            attempt(300, Verdict::Wrong, "250"),
+        ];
+        assert!(history.check(1, "300", 1000).is_ok());
+        assert!(history.check(2, "500", 1000).is_ok());
+        for answer in ["500", "600", "100", "50", "250"] {
+            assert!(history.check(1, answer, 1000).is_err(), "{}", answer);
+        }
+        history
+            .attempts
+            .push(attempt(1000, Verdict::Wait(60), "300"));
+        assert_eq!(
+            history.check(1, "301", 1030).unwrap_err().to_string(),
+            "not submitting: the server asked to wait another 30s"
+        );
+        assert!(history.check(1, "301", 1060).is_ok());
+        history
+            .attempts
+            .push(attempt(1100, Verdict::Correct, "301"));
+        assert!(history.check(1, "302", 2000).is_err());
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let mut history = temp_history("roundtrip");
+        history
+            .record(attempt(100, Verdict::TooHigh, "500"))
+            .unwrap();
+        history
+            .record(attempt(200, Verdict::Wait(60), "ab,cd"))
+            .unwrap();
+        let loaded = History::load(&history.path).unwrap();
+        assert_eq!(loaded.attempts, history.attempts);
+        fs::remove_file(&history.path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = MockServer::start(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        )]);
+        let client = server.client("submit");
+        let mut history = temp_history("submit");
+        let verdict = submit(&client, 2024, 1, 1, &Answer::Number(500), &mut history).unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        assert!(server.requests()[0].ends_with("level=1&answer=500"));
+        assert!(submit(&client, 2024, 1, 1, &Answer::Number(600), &mut history).is_err());
+        assert!(submit(&client, 2024, 1, 1, &Answer::Unsolved, &mut history).is_err());
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(History::load(&history.path).unwrap().attempts.len(), 1);
+        fs::remove_file(&history.path).unwrap();
+    }
+
+    #[test]
+    fn test_record_answer() {
+        let path = env::temp_dir().join("aoc-rust-record-answer.ans");
+        let _ = fs::remove_file(&path);
+        record_answer(&path, 2, "ab,cd").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Part 2: ab,cd\n");
+        record_answer(&path, 1, "42").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "Part 1: 42\nPart 2: ab,cd\n"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
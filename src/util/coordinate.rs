@@ -16,6 +16,20 @@ impl Coordinate {
     pub fn offset(&self, offset: &Direction) -> Coordinate {
         Coordinate::new(self.y + offset.dy, self.x + offset.dx)
     }
+
+    /// The coordinates above, right of, below and left of this one.
+    pub fn neighbors4(&self) -> impl Iterator<Item = Coordinate> {
+        let center = *self;
+        Direction::CARDINAL
+            .iter()
+            .map(move |dir| center.offset(dir))
+    }
+
+    /// The eight surrounding coordinates, clockwise from the one above.
+    pub fn neighbors8(&self) -> impl Iterator<Item = Coordinate> {
+        let center = *self;
+        Direction::ALL.iter().map(move |dir| center.offset(dir))
+    }
 }
 
 impl Ord for Coordinate {
@@ -69,6 +83,22 @@ mod tests {
         assert_eq!(a - b, Coordinate::new(-2, -2));
     }
 
+    #[test]
+    fn test_neighbors() {
+        let c = Coordinate::new(0, 0);
+        assert_eq!(
+            c.neighbors4().collect::<Vec<_>>(),
+            vec![
+                Coordinate::new(-1, 0),
+                Coordinate::new(0, 1),
+                Coordinate::new(1, 0),
+                Coordinate::new(0, -1),
+            ]
+        );
+        assert_eq!(c.neighbors8().count(), 8);
+        assert!(c.neighbors8().all(|n| n != c));
+    }
+
     #[test]
     fn test_neg() {
         let a = Coordinate::new(1, 2);
@@ -13,6 +13,24 @@ impl Direction {
     pub const BOTTOM_RIGHT: Direction = Direction { dx: 1, dy: 1 };
     pub const BOTTOM_LEFT: Direction = Direction { dx: -1, dy: 1 };
     pub const TOP_LEFT: Direction = Direction { dx: -1, dy: -1 };
+    /// Up, right, down and left, i.e. clockwise from up
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::UP,
+        Direction::RIGHT,
+        Direction::DOWN,
+        Direction::LEFT,
+    ];
+    /// The cardinal and diagonal directions, clockwise from up
+    pub const ALL: [Direction; 8] = [
+        Direction::UP,
+        Direction::TOP_RIGHT,
+        Direction::RIGHT,
+        Direction::BOTTOM_RIGHT,
+        Direction::DOWN,
+        Direction::BOTTOM_LEFT,
+        Direction::LEFT,
+        Direction::TOP_LEFT,
+    ];
 
     pub fn new(dy: isize, dx: isize) -> Direction {
         Self { dy, dx }
@@ -1,142 +1,614 @@
 use crate::util::coordinate::Coordinate;
+use crate::util::dir::Direction;
+use crate::util::rect::Rect;
+use crate::util::region::Region;
 use itertools::Itertools;
+use std::collections::HashSet;
 use std::fmt::{Debug, Display, Formatter};
 use std::mem;
+use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
-#[derive(Clone, PartialEq, Eq)]
+/// A rectangular grid stored row by row in a single `Vec`, addressed by [`Coordinate`]s with `y` as the row
+/// and `x` as the column.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
-    grid: Vec<Vec<T>>,
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
 }
 
-impl<T: Copy + Default + Debug + PartialEq<T>> Grid<T> {
+impl<T> Grid<T> {
+    /// Panics if the rows aren't all the same length.
     pub fn new(grid: Vec<Vec<T>>) -> Self {
         assert!(grid.iter().map(|row| row.len()).all_equal());
-        Self { grid }
+        let height = grid.len();
+        let width = grid.first().map_or(0, |row| row.len());
+        Self {
+            cells: grid.into_iter().flatten().collect(),
+            width,
+            height,
+        }
     }
 
-    pub fn new_with(y_len: usize, x_len: usize, initial_value: T) -> Self {
-        let mut grid = Vec::with_capacity(y_len);
-        for y in 0..y_len {
-            grid.push(Vec::with_capacity(x_len));
-            for _ in 0..x_len {
-                grid[y].push(initial_value);
+    /// Parses one cell per character with `parse`, one row per line.
+    pub fn parse_with<E>(
+        input: &str,
+        mut parse: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseGridError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for (row, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                let cell = parse(c).map_err(|_| ParseGridError::InvalidCell { row, column, c })?;
+                cells.push(cell);
+            }
+            let found = cells.len() - start;
+            match width {
+                Some(expected) if found != expected => {
+                    return Err(ParseGridError::RaggedRow {
+                        row,
+                        expected,
+                        found,
+                    })
+                }
+                _ => width = Some(found),
             }
+            height += 1;
         }
-        Self::new(grid)
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
     }
 
-    pub fn get(&self, coordinate: &Coordinate) -> Option<&T> {
-        self.grid
-            .get(coordinate.y as usize)?
-            .get(coordinate.x as usize)
+    /// Parses a grid of a cell type converting from single characters, such as an enum of tiles.
+    pub fn parse_cells(input: &str) -> Result<Self, ParseGridError>
+    where
+        T: TryFrom<char>,
+    {
+        Self::parse_with(input, T::try_from)
     }
 
-    pub fn replace(&mut self, coordinate: &Coordinate, value: T) -> Option<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn y_len(&self) -> usize {
+        self.height
+    }
+
+    pub fn x_len(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        (0..self.height as isize).contains(&coordinate.y)
+            && (0..self.width as isize).contains(&coordinate.x)
+    }
+
+    fn index_of(&self, coordinate: &Coordinate) -> Option<usize> {
         match self.contains(coordinate) {
-            true => {
-                let prev = self.grid[coordinate.y as usize][coordinate.x as usize];
-                self.grid[coordinate.y as usize][coordinate.x as usize] = value;
-                Some(prev)
-            }
+            true => Some(coordinate.y as usize * self.width + coordinate.x as usize),
             false => None,
         }
     }
 
+    pub fn get(&self, coordinate: &Coordinate) -> Option<&T> {
+        self.index_of(coordinate).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coordinate: &Coordinate) -> Option<&mut T> {
+        self.index_of(coordinate).map(|i| &mut self.cells[i])
+    }
+
+    /// The in-bounds coordinates above, right of, below and left of `coordinate`.
+    pub fn neighbors4(&self, coordinate: &Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.neighbors(coordinate, &Direction::CARDINAL)
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// The in-bounds coordinates of the eight surrounding cells, clockwise from the one above.
+    pub fn neighbors8(&self, coordinate: &Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.neighbors(coordinate, &Direction::ALL)
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// Like [`Grid::neighbors4`], with the value of each neighbour.
+    pub fn neighbors4_values(
+        &self,
+        coordinate: &Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        self.neighbors(coordinate, &Direction::CARDINAL)
+    }
+
+    /// Like [`Grid::neighbors8`], with the value of each neighbour.
+    pub fn neighbors8_values(
+        &self,
+        coordinate: &Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        self.neighbors(coordinate, &Direction::ALL)
+    }
+
+    /// The [`Grid::neighbors4`] whose value matches `predicate`, e.g. the open cells next to a position.
+    pub fn neighbors4_where<'a>(
+        &'a self,
+        coordinate: &Coordinate,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Coordinate> + 'a {
+        self.neighbors(coordinate, &Direction::CARDINAL)
+            .filter(move |(_, value)| predicate(value))
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// The [`Grid::neighbors8`] whose value matches `predicate`.
+    pub fn neighbors8_where<'a>(
+        &'a self,
+        coordinate: &Coordinate,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Coordinate> + 'a {
+        self.neighbors(coordinate, &Direction::ALL)
+            .filter(move |(_, value)| predicate(value))
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    fn neighbors(
+        &self,
+        coordinate: &Coordinate,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        let center = *coordinate;
+        directions.iter().filter_map(move |dir| {
+            let neighbor = center.offset(dir);
+            self.get(&neighbor).map(|value| (neighbor, value))
+        })
+    }
+
+    /// The region of cells reachable from `start` through 4-neighbours, where the fill spreads from a cell to a
+    /// neighbour if `spreads(cell_value, neighbor_value)`. Empty if `start` is out of bounds.
+    pub fn flood_fill(&self, start: &Coordinate, spreads: impl Fn(&T, &T) -> bool) -> Region {
+        let mut cells = HashSet::new();
+        if !self.contains(start) {
+            return Region::new(cells);
+        }
+        cells.insert(*start);
+        let mut stack = vec![*start];
+        while let Some(current) = stack.pop() {
+            let value = &self[current];
+            for (neighbor, neighbor_value) in self.neighbors4_values(&current) {
+                if spreads(value, neighbor_value) && cells.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        Region::new(cells)
+    }
+
+    /// Splits the grid into regions of 4-connected cells for which `same_region` holds between neighbours,
+    /// e.g. `|a, b| a == b` for areas of equal values. Regions are ordered by their first cell in reading
+    /// order, so a region's index can serve as its label.
+    pub fn components(&self, same_region: impl Fn(&T, &T) -> bool) -> Vec<Region> {
+        let mut labelled = vec![false; self.cells.len()];
+        let mut regions = Vec::new();
+        for index in 0..self.cells.len() {
+            if labelled[index] {
+                continue;
+            }
+            let start =
+                Coordinate::new((index / self.width) as isize, (index % self.width) as isize);
+            let region = self.flood_fill(&start, &same_region);
+            for cell in region.cells() {
+                labelled[cell.y as usize * self.width + cell.x as usize] = true;
+            }
+            regions.push(region);
+        }
+        regions
+    }
+
+    /// The values of row `y`. Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {} out of bounds", y);
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, top to bottom. Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> Line<'_, T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        Line {
+            grid: self,
+            next: x,
+            end: self.cells.len(),
+            step: self.width,
+        }
+    }
+
+    /// Every row as an iterator over its cells, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = Line<'_, T>> + '_ {
+        (0..self.height).map(|y| Line {
+            grid: self,
+            next: y * self.width,
+            end: (y + 1) * self.width,
+            step: 1,
+        })
+    }
+
+    /// Every column as an iterator over its cells, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = Line<'_, T>> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The indexes of the rows whose values all match `predicate`.
+    pub fn empty_rows(&self, predicate: impl Fn(&T) -> bool) -> Vec<usize> {
+        (0..self.height)
+            .filter(|&y| self.row(y).iter().all(&predicate))
+            .collect()
+    }
+
+    /// The indexes of the columns whose values all match `predicate`.
+    pub fn empty_columns(&self, predicate: impl Fn(&T) -> bool) -> Vec<usize> {
+        (0..self.width)
+            .filter(|&x| self.column(x).all(|(_, value)| predicate(value)))
+            .collect()
+    }
+
+    /// Inserts `row` before row `y`, or at the bottom if `y` is the height. The row sets the width of a grid
+    /// without rows, and must match it otherwise.
+    pub fn insert_row(&mut self, y: usize, row: Vec<T>) {
+        assert!(y <= self.height, "row {} out of bounds", y);
+        if self.height == 0 {
+            self.width = row.len();
+        }
+        assert_eq!(row.len(), self.width, "row length doesn't match the width");
+        let index = y * self.width;
+        self.cells.splice(index..index, row);
+        self.height += 1;
+    }
+
+    /// Inserts `column` before column `x`, or at the right if `x` is the width. The column sets the height of a
+    /// grid without columns, and must match it otherwise.
+    pub fn insert_column(&mut self, x: usize, column: Vec<T>) {
+        assert!(x <= self.width, "column {} out of bounds", x);
+        if self.width == 0 {
+            self.height = column.len();
+        }
+        assert_eq!(
+            column.len(),
+            self.height,
+            "column length doesn't match the height"
+        );
+        let mut old = mem::take(&mut self.cells).into_iter();
+        let mut cells = Vec::with_capacity(self.height * (self.width + 1));
+        for value in column {
+            cells.extend(old.by_ref().take(x));
+            cells.push(value);
+            cells.extend(old.by_ref().take(self.width - x));
+        }
+        self.cells = cells;
+        self.width += 1;
+    }
+
+    /// Removes row `y`, returning its values. Panics if `y` is out of bounds.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(y < self.height, "row {} out of bounds", y);
+        self.height -= 1;
+        self.cells
+            .drain(y * self.width..(y + 1) * self.width)
+            .collect()
+    }
+
+    /// Removes column `x`, returning its values. Panics if `x` is out of bounds.
+    pub fn remove_column(&mut self, x: usize) -> Vec<T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        let width = self.width;
+        let (column, cells) = mem::take(&mut self.cells)
+            .into_iter()
+            .enumerate()
+            .partition::<Vec<_>, _>(|(index, _)| index % width == x);
+        self.cells = cells.into_iter().map(|(_, value)| value).collect();
+        self.width -= 1;
+        column.into_iter().map(|(_, value)| value).collect()
+    }
+
+    pub fn replace(&mut self, coordinate: &Coordinate, value: T) -> Option<T> {
+        self.get_mut(coordinate)
+            .map(|cell| mem::replace(cell, value))
+    }
+
     pub fn swap(&mut self, a: &Coordinate, b: &Coordinate) -> Option<()> {
-        match (self.contains(a), self.contains(b)) {
-            (true, true) => {
-                let a_value = mem::take(&mut self.grid[a.y as usize][a.x as usize]);
-                let b_value = mem::replace(&mut self.grid[b.y as usize][b.x as usize], a_value);
-                self.grid[a.y as usize][a.x as usize] = b_value;
+        match (self.index_of(a), self.index_of(b)) {
+            (Some(a), Some(b)) => {
+                self.cells.swap(a, b);
                 Some(())
             }
             _ => None,
         }
     }
 
-    pub fn contains(&self, coordinate: &Coordinate) -> bool {
-        coordinate.y < self.grid.len() as isize
-            && coordinate.y >= 0
-            && coordinate.x < self.grid[0].len() as isize
-            && coordinate.x >= 0
+    pub fn iter(&self) -> GridIterator<'_, T> {
+        GridIterator {
+            grid: self,
+            index: 0,
+        }
     }
 
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.iter().map(|(coordinate, _)| coordinate)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn flip_h(&mut self) {
+        if self.width > 0 {
+            self.cells
+                .chunks_mut(self.width)
+                .for_each(|row| row.reverse());
+        }
+    }
+
+    pub fn flip_v(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.cells.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    /// A borrowed view of the cells within `rect`, addressed relative to its top left corner.
+    /// `None` unless `rect` lies within the grid.
+    pub fn view(&self, rect: Rect) -> Option<GridView<'_, T>> {
+        let empty = rect.height() == 0 || rect.width() == 0;
+        match empty || (self.contains(&rect.min) && self.contains(&rect.max)) {
+            true => Some(GridView { grid: self, rect }),
+            false => None,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new_with(y_len: usize, x_len: usize, initial_value: T) -> Self {
+        Self {
+            cells: vec![initial_value; y_len * x_len],
+            width: x_len,
+            height: y_len,
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
     pub fn find(&self, value: &T) -> Option<Coordinate> {
-        self.coordinates()
-            .find(|coordinate| self.get(coordinate) == Some(value))
+        self.iter()
+            .find(|(_, v)| *v == value)
+            .map(|(coordinate, _)| coordinate)
     }
 
     pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Coordinate> + 'a {
-        self.coordinates()
-            .filter(move |coordinate| self.get(coordinate) == Some(value))
+        self.iter()
+            .filter(move |(_, v)| *v == value)
+            .map(|(coordinate, _)| coordinate)
     }
+}
 
+impl<T: Copy> Grid<T> {
     pub fn get_segment(&self, start: &Coordinate, dx: isize, dy: isize, len: usize) -> Vec<T> {
         let mut result = Vec::new();
         let mut current = Coordinate::new(start.y, start.x);
         for _ in 0..len {
-            if !self.contains(&current) {
-                break;
+            match self.get(&current) {
+                Some(value) => result.push(*value),
+                None => break,
             }
-            result.push(self.grid[current.y as usize][current.x as usize]);
             current = Coordinate::new(current.y + dy, current.x + dx);
         }
         result
     }
 
-    pub fn iter(&self) -> GridIterator<T> {
-        GridIterator {
-            grid: self,
-            y: 0,
-            x: 0,
+    /// A grid of `height` rows and `width` columns with the value of each cell given by `value(y, x)`.
+    fn from_fn(height: usize, width: usize, value: impl Fn(usize, usize) -> T) -> Self {
+        Self {
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (y, x)))
+                .map(|(y, x)| value(y, x))
+                .collect(),
+            width,
+            height,
         }
     }
 
-    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
-        self.iter().map(|(coordinate, _)| coordinate)
+    /// The grid turned a quarter clockwise, so the first column becomes the first row, bottom to top.
+    pub fn rotated_cw(&self) -> Self {
+        let height = self.height;
+        Self::from_fn(self.width, self.height, |y, x| {
+            self.cells[(height - 1 - x) * self.width + y]
+        })
     }
 
-    #[allow(dead_code)]
-    pub fn values(&self) -> impl Iterator<Item = &T> {
-        self.iter().map(|(_, value)| value)
+    /// The grid turned a quarter counterclockwise, so the last column becomes the first row.
+    pub fn rotated_ccw(&self) -> Self {
+        let width = self.width;
+        Self::from_fn(self.width, self.height, |y, x| {
+            self.cells[x * width + (width - 1 - y)]
+        })
     }
 
-    pub fn y_len(&self) -> usize {
-        self.grid.len()
+    /// The grid mirrored along its main diagonal, so rows become columns.
+    pub fn transposed(&self) -> Self {
+        Self::from_fn(self.width, self.height, |y, x| {
+            self.cells[x * self.width + y]
+        })
     }
 
-    pub fn x_len(&self) -> usize {
-        self.grid[0].len()
+    /// The grid mirrored left to right.
+    pub fn flipped_h(&self) -> Self {
+        let mut flipped = self.clone();
+        flipped.flip_h();
+        flipped
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flipped_v(&self) -> Self {
+        let mut flipped = self.clone();
+        flipped.flip_v();
+        flipped
+    }
+
+    pub fn rotate_cw(&mut self) {
+        *self = self.rotated_cw();
+    }
+
+    pub fn rotate_ccw(&mut self) {
+        *self = self.rotated_ccw();
     }
+
+    pub fn transpose(&mut self) {
+        *self = self.transposed();
+    }
+}
+
+/// A rectangular part of a [`Grid`], see [`Grid::view`]. Coordinates are relative to the view's top left
+/// corner, and [`GridView::to_parent`] maps them to the grid's.
+#[derive(Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
 }
 
-#[derive(Debug)]
-pub struct ParseGridError {}
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.rect.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rect.height()
+    }
+
+    /// The area of the grid this is a view of.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        (0..self.height() as isize).contains(&coordinate.y)
+            && (0..self.width() as isize).contains(&coordinate.x)
+    }
+
+    pub fn to_parent(&self, coordinate: &Coordinate) -> Coordinate {
+        *coordinate + self.rect.min
+    }
+
+    pub fn from_parent(&self, coordinate: &Coordinate) -> Coordinate {
+        *coordinate - self.rect.min
+    }
+
+    pub fn get(&self, coordinate: &Coordinate) -> Option<&'a T> {
+        match self.contains(coordinate) {
+            true => self.grid.get(&self.to_parent(coordinate)),
+            false => None,
+        }
+    }
+
+    /// The values of row `y` of the view. Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.height(), "row {} out of bounds", y);
+        let start = (self.rect.min.y as usize + y) * self.grid.width + self.rect.min.x as usize;
+        &self.grid.cells[start..start + self.width()]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &'a T)> + '_ {
+        (0..self.height()).flat_map(move |y| {
+            self.row(y)
+                .iter()
+                .enumerate()
+                .map(move |(x, value)| (Coordinate::new(y as isize, x as isize), value))
+        })
+    }
+}
 
-impl<T> FromStr for Grid<T>
-where
-    T: FromStr + Default + Debug + Copy + PartialEq<T>,
-{
+impl<T: Clone> GridView<'_, T> {
+    /// Copies the viewed cells into a grid of their own.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            cells: (0..self.height())
+                .flat_map(|y| self.row(y).iter().cloned())
+                .collect(),
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &T {
+        match self.get(&coordinate) {
+            Some(value) => value,
+            None => panic!("{:?} out of bounds", coordinate),
+        }
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, coordinate: Coordinate) -> &mut T {
+        match self.get_mut(&coordinate) {
+            Some(value) => value,
+            None => panic!("{:?} out of bounds", coordinate),
+        }
+    }
+}
+
+/// Why a grid couldn't be parsed. Rows and columns are 0-based, like a [`Coordinate`]'s `y` and `x`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// The character `c` at `row`, `column` isn't a valid cell
+    InvalidCell { row: usize, column: usize, c: char },
+    /// Row `row` has `found` cells, where the rows before it have `expected`
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::InvalidCell { row, column, c } => {
+                write!(f, "invalid cell {:?} at row {}, column {}", c, row, column)
+            }
+            ParseGridError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {} has {} cells, expected {}", row, found, expected),
+        }
+    }
+}
+
+impl<T: FromStr> FromStr for Grid<T> {
     type Err = ParseGridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid: Result<Vec<Vec<T>>, ParseGridError> = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| T::from_str(&c.to_string()).map_err(|_| ParseGridError {}))
-                    .collect()
-            })
-            .collect();
-        grid.map(Grid::new)
+        let mut buffer = [0; 4];
+        Grid::parse_with(s, |c| T::from_str(c.encode_utf8(&mut buffer)))
     }
 }
 
 impl<T: Debug> Debug for Grid<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.grid {
-            for item in row {
+        for y in 0..self.height {
+            for item in self.row(y) {
                 write!(f, "{:?}", item)?;
             }
             writeln!(f)?;
@@ -147,8 +619,8 @@ impl<T: Debug> Debug for Grid<T> {
 
 impl<T: Display> Display for Grid<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in &self.grid {
-            for item in row {
+        for y in 0..self.height {
+            for item in self.row(y) {
                 write!(f, "{}", item)?;
             }
             writeln!(f)?;
@@ -157,36 +629,58 @@ impl<T: Display> Display for Grid<T> {
     }
 }
 
-pub struct GridIterator<'a, T>
-where
-    T: Copy + PartialEq<T> + Debug,
-{
+/// The cells of a row or column, see [`Grid::rows`] and [`Grid::columns`].
+pub struct Line<'a, T> {
     grid: &'a Grid<T>,
-    y: usize,
-    x: usize,
+    /// Index of the next cell, stepping by `step` until `end`
+    next: usize,
+    end: usize,
+    step: usize,
 }
 
-impl<'a, T> Iterator for GridIterator<'a, T>
-where
-    T: Copy + Default + Debug + PartialEq<T>,
-{
+impl<'a, T> Iterator for Line<'a, T> {
     type Item = (Coordinate, &'a T);
     fn next(&mut self) -> Option<Self::Item> {
-        if self.x < self.grid.x_len() {
-            let coordinate = Coordinate::new(self.y as isize, self.x as isize);
-            let value = match self.grid.get(&coordinate) {
-                Some(value) => value,
-                None => unreachable!(),
-            };
-            self.x += 1;
-            return Some((coordinate, value));
+        if self.next >= self.end {
+            return None;
         }
-        self.y += 1;
-        if self.y < self.grid.y_len() {
-            self.x = 0;
-            return self.next();
-        }
-        None
+        let index = self.next;
+        self.next += self.step;
+        let coordinate = Coordinate::new(
+            (index / self.grid.width) as isize,
+            (index % self.grid.width) as isize,
+        );
+        Some((coordinate, &self.grid.cells[index]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end.saturating_sub(self.next)).div_ceil(self.step);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Line<'_, T> {}
+
+pub struct GridIterator<'a, T> {
+    grid: &'a Grid<T>,
+    index: usize,
+}
+
+impl<'a, T> Iterator for GridIterator<'a, T> {
+    type Item = (Coordinate, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.grid.cells.get(self.index)?;
+        let coordinate = Coordinate::new(
+            (self.index / self.grid.width) as isize,
+            (self.index % self.grid.width) as isize,
+        );
+        self.index += 1;
+        Some((coordinate, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.grid.cells.len() - self.index;
+        (remaining, Some(remaining))
     }
 }
 
@@ -212,6 +706,282 @@ mod tests {
         assert_eq!(grid.get(&Coordinate::new(0, 1)), Some(&"b"));
     }
 
+    #[test]
+    fn test_dimensions() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!((grid.x_len(), grid.y_len()), (3, 2));
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid = Grid::<u8>::new(vec![]);
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert!(grid.is_empty());
+        assert!(!grid.contains(&Coordinate::new(0, 0)));
+        assert_eq!(grid.iter().count(), 0);
+        assert_eq!(grid.find(&0), None);
+        assert_eq!(format!("{}", grid), "");
+        let grid = Grid::new_with(2, 0, 'x');
+        assert_eq!((grid.width(), grid.height()), (0, 2));
+        assert_eq!(grid.iter().count(), 0);
+        assert_eq!(grid.row(1), &[] as &[char]);
+        assert_eq!("".parse::<Grid<char>>().unwrap().height(), 0);
+    }
+
+    #[test]
+    fn test_get_mut_and_index() {
+        let mut grid = Grid::new_with(2, 3, 0);
+        *grid.get_mut(&Coordinate::new(1, 2)).unwrap() = 5;
+        grid[Coordinate::new(0, 1)] += 2;
+        assert_eq!(grid[Coordinate::new(1, 2)], 5);
+        assert_eq!(
+            grid.values().copied().collect::<Vec<_>>(),
+            vec![0, 2, 0, 0, 0, 5]
+        );
+        assert!(grid.get_mut(&Coordinate::new(-1, 0)).is_none());
+        assert!(grid.get(&Coordinate::new(0, 3)).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let grid = Grid::new(vec![vec![1, 2]]);
+        let _ = grid[Coordinate::new(0, 2)];
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let corner = Coordinate::new(0, 0);
+        assert_eq!(
+            grid.neighbors4(&corner).collect::<Vec<_>>(),
+            vec![Coordinate::new(0, 1), Coordinate::new(1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors8_values(&corner)
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![2, 5, 4]
+        );
+        let center = Coordinate::new(1, 1);
+        assert_eq!(
+            grid.neighbors4_values(&center)
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![2, 6, 4]
+        );
+        assert_eq!(grid.neighbors8(&center).count(), 5);
+        assert_eq!(
+            grid.neighbors8_where(&center, |v| v % 2 == 0)
+                .collect::<Vec<_>>(),
+            vec![
+                Coordinate::new(0, 1),
+                Coordinate::new(1, 2),
+                Coordinate::new(1, 0)
+            ]
+        );
+        assert_eq!(grid.neighbors4_where(&center, |v| *v > 5).count(), 1);
+        assert_eq!(grid.neighbors4(&Coordinate::new(5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid: Grid<u32> = "0123\n1234\n8765".parse().unwrap();
+        let trail = grid.flood_fill(&Coordinate::new(0, 0), |a, b| *b == a + 1);
+        assert_eq!(trail.area(), 12);
+        let flat = grid.flood_fill(&Coordinate::new(2, 0), |a, b| *b == a + 1);
+        assert_eq!(flat.area(), 1);
+        let low = grid.flood_fill(&Coordinate::new(0, 0), |_, b| *b < 3);
+        assert_eq!(low.area(), 5);
+        assert_eq!(
+            grid.flood_fill(&Coordinate::new(3, 0), |_, _| true).area(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let grid: Grid<char> = "AAAA\nBBCD\nBBCC\nEEEC".parse().unwrap();
+        let regions = grid.components(|a, b| a == b);
+        let summary = regions
+            .iter()
+            .map(|r| {
+                (
+                    grid[*r.cells().iter().next().unwrap()],
+                    r.area(),
+                    r.perimeter(),
+                    r.sides(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+        assert!(Grid::<char>::new(vec![])
+            .components(|a, b| a == b)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let cw = grid.rotated_cw();
+        assert_eq!(cw, Grid::new(vec![vec![4, 1], vec![5, 2], vec![6, 3]]));
+        assert_eq!(
+            grid.rotated_ccw(),
+            Grid::new(vec![vec![3, 6], vec![2, 5], vec![1, 4]])
+        );
+        assert_eq!(cw.rotated_ccw(), grid);
+        let mut turned = grid.clone();
+        (0..4).for_each(|_| turned.rotate_cw());
+        assert_eq!(turned, grid);
+        turned.rotate_ccw();
+        assert_eq!(turned, grid.rotated_ccw());
+    }
+
+    #[test]
+    fn test_transpose_and_flip() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(
+            grid.transposed(),
+            Grid::new(vec![vec![1, 4], vec![2, 5], vec![3, 6]])
+        );
+        assert_eq!(
+            grid.flipped_h(),
+            Grid::new(vec![vec![3, 2, 1], vec![6, 5, 4]])
+        );
+        assert_eq!(
+            grid.flipped_v(),
+            Grid::new(vec![vec![4, 5, 6], vec![1, 2, 3]])
+        );
+        let mut odd = Grid::new(vec![vec![1], vec![2], vec![3]]);
+        odd.flip_v();
+        assert_eq!(odd, Grid::new(vec![vec![3], vec![2], vec![1]]));
+        odd.transpose();
+        assert_eq!(odd, Grid::new(vec![vec![3, 2, 1]]));
+        let mut empty = Grid::<u8>::new(vec![]);
+        empty.flip_h();
+        empty.rotate_cw();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_view() {
+        let grid: Grid<u32> = "1234\n5678\n9012".parse().unwrap();
+        let view = grid
+            .view(Rect::with_size(Coordinate::new(1, 1), 2, 2))
+            .unwrap();
+        assert_eq!((view.height(), view.width()), (2, 2));
+        assert_eq!(view.get(&Coordinate::new(0, 0)), Some(&6));
+        assert_eq!(view.get(&Coordinate::new(1, 1)), Some(&1));
+        assert_eq!(view.get(&Coordinate::new(0, 2)), None);
+        assert_eq!(view.row(1), &[0, 1]);
+        assert_eq!(
+            view.to_parent(&Coordinate::new(1, 0)),
+            Coordinate::new(2, 1)
+        );
+        assert_eq!(
+            view.from_parent(&Coordinate::new(2, 1)),
+            Coordinate::new(1, 0)
+        );
+        assert_eq!(
+            view.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec![6, 7, 0, 1]
+        );
+        assert_eq!(view.to_grid().to_string(), "67\n01\n");
+        assert!(grid
+            .view(Rect::with_size(Coordinate::new(2, 2), 2, 2))
+            .is_none());
+        let empty = grid
+            .view(Rect::with_size(Coordinate::new(0, 0), 0, 3))
+            .unwrap();
+        assert_eq!(empty.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let rows = grid
+            .rows()
+            .map(|row| row.map(|(_, v)| *v).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let columns = grid
+            .columns()
+            .map(|column| column.map(|(_, v)| *v).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        let column = grid.column(2);
+        assert_eq!(column.len(), 2);
+        assert_eq!(
+            column.map(|(c, _)| c).collect::<Vec<_>>(),
+            vec![Coordinate::new(0, 2), Coordinate::new(1, 2)]
+        );
+        assert_eq!(grid.rows().nth(1).unwrap().len(), 3);
+        assert_eq!(
+            Grid::new_with(2, 0, 0)
+                .rows()
+                .map(|row| row.len())
+                .sum::<usize>(),
+            0
+        );
+        assert_eq!(Grid::new_with(2, 0, 0).columns().count(), 0);
+    }
+
+    #[test]
+    fn test_empty_rows_and_columns() {
+        let grid: Grid<char> = "#..\n...\n..#".parse().unwrap();
+        assert_eq!(grid.empty_rows(|&c| c == '.'), vec![1]);
+        assert_eq!(grid.empty_columns(|&c| c == '.'), vec![1]);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+        grid.insert_row(1, vec![5, 6]);
+        assert_eq!(grid.to_string(), "12\n56\n34\n");
+        grid.insert_column(2, vec![7, 8, 9]);
+        assert_eq!(grid.to_string(), "127\n568\n349\n");
+        grid.insert_column(0, vec![0, 0, 0]);
+        assert_eq!(grid.remove_column(2), vec![2, 6, 4]);
+        assert_eq!(grid.remove_row(0), vec![0, 1, 7]);
+        assert_eq!(grid.to_string(), "058\n039\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        let mut empty = Grid::new(vec![]);
+        empty.insert_column(0, vec![1, 2]);
+        empty.insert_row(2, vec![3]);
+        assert_eq!(empty.to_string(), "1\n2\n3\n");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_row_wrong_length() {
+        Grid::new(vec![vec![1, 2]]).insert_row(0, vec![3]);
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut grid = Grid::new(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(
+            grid.swap(&Coordinate::new(0, 0), &Coordinate::new(1, 1)),
+            Some(())
+        );
+        assert_eq!(grid.to_string(), "42\n31\n");
+        assert_eq!(
+            grid.swap(&Coordinate::new(0, 0), &Coordinate::new(2, 0)),
+            None
+        );
+    }
+
     #[test]
     fn test_get_invalid_coordinate() {
         let grid = Grid::new(vec![vec!["a", "b"], vec!["c", "d"]]);
@@ -310,7 +1080,69 @@ mod tests {
     fn test_parse_grid_from_str_invalid_characters() {
         let input = "1a\n34";
         let result: Result<Grid<u32>, _> = input.parse();
-        assert!(result.is_err());
+        assert_eq!(
+            result,
+            Err(ParseGridError::InvalidCell {
+                row: 0,
+                column: 1,
+                c: 'a'
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_grid_ragged_rows() {
+        let result: Result<Grid<char>, _> = "abc\nabc\nab\nabc".parse();
+        let error = result.unwrap_err();
+        assert_eq!(
+            error,
+            ParseGridError::RaggedRow {
+                row: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(error.to_string(), "row 2 has 2 cells, expected 3");
+        assert_eq!(Grid::<char>::from_str("").unwrap().height(), 0);
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let wall = |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(()),
+        };
+        let grid = Grid::parse_with("#.\n.#", wall).unwrap();
+        assert_eq!(grid, Grid::new(vec![vec![true, false], vec![false, true]]));
+        let error = Grid::parse_with("#.\n.x", wall).unwrap_err();
+        assert_eq!(error.to_string(), "invalid cell 'x' at row 1, column 1");
+    }
+
+    #[test]
+    fn test_parse_cells() {
+        #[derive(Debug, PartialEq)]
+        enum Tile {
+            Wall,
+            Open,
+        }
+
+        impl TryFrom<char> for Tile {
+            type Error = char;
+
+            fn try_from(c: char) -> Result<Self, Self::Error> {
+                match c {
+                    '#' => Ok(Tile::Wall),
+                    '.' => Ok(Tile::Open),
+                    _ => Err(c),
+                }
+            }
+        }
+
+        let grid = Grid::<Tile>::parse_cells("#.#\n...").unwrap();
+        assert_eq!(grid.get(&Coordinate::new(0, 2)), Some(&Tile::Wall));
+        assert_eq!(grid.get(&Coordinate::new(1, 0)), Some(&Tile::Open));
+        assert!(Grid::<Tile>::parse_cells("#S").is_err());
     }
 
     #[test]
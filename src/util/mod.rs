@@ -1,3 +1,8 @@
 pub mod coordinate;
 pub mod dir;
 pub mod grid;
+pub mod rect;
+pub mod region;
+pub mod search;
+pub mod sparse_grid;
+pub mod wrapping_grid;
@@ -0,0 +1,89 @@
+use crate::util::coordinate::Coordinate;
+
+/// An axis-aligned rectangle of coordinates, `min` and `max` included.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Rect {
+    pub min: Coordinate,
+    pub max: Coordinate,
+}
+
+impl Rect {
+    pub fn new(min: Coordinate, max: Coordinate) -> Rect {
+        Self { min, max }
+    }
+
+    /// The rectangle of `height` rows and `width` columns with `top_left` as its first coordinate.
+    pub fn with_size(top_left: Coordinate, height: usize, width: usize) -> Rect {
+        Rect::new(
+            top_left,
+            Coordinate::new(
+                top_left.y + height as isize - 1,
+                top_left.x + width as isize - 1,
+            ),
+        )
+    }
+
+    /// The smallest rectangle containing all `coordinates`, if there are any.
+    pub fn bounding(coordinates: impl IntoIterator<Item = Coordinate>) -> Option<Rect> {
+        coordinates.into_iter().fold(None, |rect, c| {
+            Some(match rect {
+                None => Rect::new(c, c),
+                Some(rect) => rect.expanded_to(&c),
+            })
+        })
+    }
+
+    /// The smallest rectangle containing both this one and `coordinate`.
+    pub fn expanded_to(&self, coordinate: &Coordinate) -> Rect {
+        Rect::new(
+            Coordinate::new(self.min.y.min(coordinate.y), self.min.x.min(coordinate.x)),
+            Coordinate::new(self.max.y.max(coordinate.y), self.max.x.max(coordinate.x)),
+        )
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1).max(0) as usize
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1).max(0) as usize
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        (self.min.y..=self.max.y).contains(&coordinate.y)
+            && (self.min.x..=self.max.x).contains(&coordinate.x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounding() {
+        let rect = Rect::bounding([
+            Coordinate::new(2, -1),
+            Coordinate::new(-3, 4),
+            Coordinate::new(0, 0),
+        ])
+        .unwrap();
+        assert_eq!(
+            rect,
+            Rect::new(Coordinate::new(-3, -1), Coordinate::new(2, 4))
+        );
+        assert_eq!((rect.height(), rect.width()), (6, 6));
+        assert!(rect.contains(&Coordinate::new(-3, 4)));
+        assert!(!rect.contains(&Coordinate::new(3, 0)));
+        assert_eq!(Rect::bounding([]), None);
+    }
+
+    #[test]
+    fn test_with_size() {
+        let rect = Rect::with_size(Coordinate::new(1, 2), 3, 4);
+        assert_eq!(rect.max, Coordinate::new(3, 5));
+        assert_eq!((rect.height(), rect.width()), (3, 4));
+        let empty = Rect::with_size(Coordinate::new(1, 2), 0, 4);
+        assert_eq!(empty.height(), 0);
+        assert!(!empty.contains(&Coordinate::new(1, 2)));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::util::coordinate::Coordinate;
+use crate::util::dir::Direction;
+use crate::util::rect::Rect;
+use std::collections::HashSet;
+
+/// A set of cells connected through their 4-neighbours, as found by
+/// [`Grid::flood_fill`](crate::util::grid::Grid::flood_fill).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Coordinate>,
+}
+
+impl Region {
+    pub fn new(cells: HashSet<Coordinate>) -> Self {
+        Self { cells }
+    }
+
+    pub fn cells(&self) -> &HashSet<Coordinate> {
+        &self.cells
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        self.cells.contains(coordinate)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges bordering a cell outside the region.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .flat_map(|c| c.neighbors4())
+            .filter(|neighbor| !self.contains(neighbor))
+            .count()
+    }
+
+    /// The number of straight sides of the region's outline, holes included, which equals its number of corners.
+    pub fn sides(&self) -> usize {
+        const DIAGONALS: [Direction; 4] = [
+            Direction::TOP_RIGHT,
+            Direction::BOTTOM_RIGHT,
+            Direction::BOTTOM_LEFT,
+            Direction::TOP_LEFT,
+        ];
+        self.cells
+            .iter()
+            .map(|c| {
+                (0..4)
+                    .filter(|&i| {
+                        let first = self.contains(&c.offset(&Direction::CARDINAL[i]));
+                        let second = self.contains(&c.offset(&Direction::CARDINAL[(i + 1) % 4]));
+                        let diagonal = self.contains(&c.offset(&DIAGONALS[i]));
+                        // An outer corner, or an inner one where the region turns around this cell
+                        (!first && !second) || (first && second && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The smallest rectangle containing the region, `None` if it's empty.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        Rect::bounding(self.cells.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(cells: &[(isize, isize)]) -> Region {
+        Region::new(cells.iter().map(|&(y, x)| Coordinate::new(y, x)).collect())
+    }
+
+    #[test]
+    fn test_square() {
+        let square = region(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+        assert_eq!(square.area(), 4);
+        assert_eq!(square.perimeter(), 8);
+        assert_eq!(square.sides(), 4);
+        assert_eq!(
+            square.bounding_box(),
+            Some(Rect::new(Coordinate::new(0, 0), Coordinate::new(1, 1)))
+        );
+    }
+
+    #[test]
+    fn test_l_shape() {
+        let l = region(&[(0, 0), (1, 0), (2, 0), (2, 1)]);
+        assert_eq!(l.perimeter(), 10);
+        assert_eq!(l.sides(), 6);
+    }
+
+    #[test]
+    fn test_ring() {
+        let ring = region(&[
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 0),
+            (1, 2),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+        ]);
+        assert_eq!(ring.perimeter(), 16);
+        assert_eq!(ring.sides(), 8);
+        assert!(!ring.contains(&Coordinate::new(1, 1)));
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = Region::default();
+        assert_eq!((empty.area(), empty.perimeter(), empty.sides()), (0, 0, 0));
+        assert_eq!(empty.bounding_box(), None);
+    }
+}
@@ -0,0 +1,304 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The outcome of a search from a start state: the cost of every state that was reached and, for each, all the
+/// states it can be reached from at that cost.
+///
+/// The search stops once every goal state at the lowest cost has been found, so states costing more than the
+/// goal may be missing. Without a reachable goal, every reachable state is explored.
+#[derive(Debug)]
+pub struct Search<S> {
+    start: S,
+    distances: HashMap<S, usize>,
+    predecessors: HashMap<S, Vec<S>>,
+    targets: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new(start: S) -> Self {
+        Self {
+            distances: HashMap::from([(start.clone(), 0)]),
+            start,
+            predecessors: HashMap::new(),
+            targets: Vec::new(),
+        }
+    }
+
+    /// Records reaching `next` from `state` at `cost`, returning whether that's an improvement.
+    fn relax(&mut self, state: &S, next: S, cost: usize) -> bool {
+        match self.distances.get(&next) {
+            Some(&distance) if cost > distance => false,
+            Some(&distance) if cost == distance => {
+                self.predecessors
+                    .entry(next)
+                    .or_default()
+                    .push(state.clone());
+                false
+            }
+            _ => {
+                self.distances.insert(next.clone(), cost);
+                self.predecessors.insert(next, vec![state.clone()]);
+                true
+            }
+        }
+    }
+
+    /// The cost of the cheapest goal state, if one was reached.
+    pub fn cost(&self) -> Option<usize> {
+        self.targets.first().map(|target| self.distances[target])
+    }
+
+    /// The goal states reached at the lowest cost.
+    pub fn targets(&self) -> &[S] {
+        &self.targets
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, usize> {
+        &self.distances
+    }
+
+    pub fn into_distances(self) -> HashMap<S, usize> {
+        self.distances
+    }
+
+    /// All the states `state` is reached from at its lowest cost.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One cheapest path from the start to `to`, both included.
+    pub fn path(&self, to: &S) -> Option<Vec<S>> {
+        self.distances.get(to)?;
+        let mut path = vec![to.clone()];
+        while let Some(previous) = self
+            .predecessors(path.last()?)
+            .first()
+            .filter(|_| path.last() != Some(&self.start))
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every cheapest path from the start to `to`. There can be exponentially many of these.
+    pub fn all_paths(&self, to: &S) -> Vec<Vec<S>> {
+        if !self.distances.contains_key(to) {
+            return Vec::new();
+        }
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![to.clone()]];
+        while let Some(path) = stack.pop() {
+            let last = path.last().expect("paths are never empty");
+            if *last == self.start {
+                paths.push(path.into_iter().rev().collect());
+                continue;
+            }
+            for previous in self.predecessors(last) {
+                let mut path = path.clone();
+                path.push(previous.clone());
+                stack.push(path);
+            }
+        }
+        paths
+    }
+
+    /// The states on any cheapest path to any of the [`Search::targets`].
+    pub fn on_best_paths(&self) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut stack = self.targets.clone();
+        while let Some(state) = stack.pop() {
+            if seen.insert(state.clone()) {
+                stack.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth-first search where every step costs 1.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, cost)) = queue.pop_front() {
+        if search.cost().is_some_and(|best| cost > best) {
+            break;
+        }
+        if is_goal(&state) {
+            search.targets.push(state);
+            continue;
+        }
+        for next in successors(&state) {
+            if search.relax(&state, next.clone(), cost + 1) {
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm, for successors given with the cost of the step to them.
+pub fn dijkstra<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, successors, |_| 0, is_goal)
+}
+
+/// A* search, like [`dijkstra`] guided by a `heuristic` estimating the remaining cost to a goal. The heuristic
+/// must never overestimate, and must be consistent for [`Search::predecessors`] to be complete.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut search = Search::new(start.clone());
+    // The heap holds indexes into `states`, so states don't have to be `Ord`
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut states = vec![start];
+    while let Some(Reverse((estimate, cost, index))) = heap.pop() {
+        if search.cost().is_some_and(|best| estimate > best) {
+            break;
+        }
+        let state = states[index].clone();
+        if cost > search.distances[&state] {
+            continue;
+        }
+        if is_goal(&state) {
+            search.targets.push(state);
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            if search.relax(&state, next.clone(), cost + step) {
+                heap.push(Reverse((
+                    cost + step + heuristic(&next),
+                    cost + step,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::coordinate::Coordinate;
+    use crate::util::grid::Grid;
+
+    const MAZE: &str = "S..#
+.#..
+...E";
+
+    fn open_neighbors(grid: &Grid<char>, c: &Coordinate) -> Vec<Coordinate> {
+        grid.neighbors4_where(c, |&v| v != '#').collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        let search = bfs(start, |c| open_neighbors(&grid, c), |c| *c == end);
+        assert_eq!(search.cost(), Some(5));
+        assert_eq!(search.targets(), &[end]);
+        let path = search.path(&end).unwrap();
+        assert_eq!((path[0], path[5]), (start, end));
+        assert_eq!(search.all_paths(&end).len(), 3);
+        assert_eq!(search.on_best_paths().len(), 10);
+        assert_eq!(search.distance(&Coordinate::new(1, 0)), Some(1));
+        assert_eq!(search.path(&Coordinate::new(9, 9)), None);
+    }
+
+    #[test]
+    fn test_bfs_without_goal() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let search = bfs(
+            Coordinate::new(0, 0),
+            |c| open_neighbors(&grid, c),
+            |_| false,
+        );
+        assert_eq!(search.cost(), None);
+        assert_eq!(search.distances().len(), 10);
+        assert_eq!(search.distance(&Coordinate::new(1, 3)), Some(4));
+        assert_eq!(search.distance(&Coordinate::new(0, 3)), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Going through the middle costs 10 per step, around it 1
+        let graph = HashMap::from([
+            ('a', vec![('b', 1), ('c', 10)]),
+            ('b', vec![('c', 1), ('d', 3)]),
+            ('c', vec![('d', 1)]),
+        ]);
+        let successors = |node: &char| graph.get(node).cloned().unwrap_or_default();
+        let search = dijkstra('a', successors, |&node| node == 'd');
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.distance(&'c'), Some(2));
+        assert_eq!(search.predecessors(&'d'), &['c']);
+        assert_eq!(search.path(&'d').unwrap(), vec!['a', 'b', 'c', 'd']);
+        let search = dijkstra('a', successors, |&node| node == 'a');
+        assert_eq!(search.cost(), Some(0));
+        assert_eq!(search.path(&'a').unwrap(), vec!['a']);
+    }
+
+    #[test]
+    fn test_dijkstra_equal_paths() {
+        let graph = HashMap::from([
+            ('a', vec![('b', 2), ('c', 1)]),
+            ('b', vec![('d', 1)]),
+            ('c', vec![('d', 2), ('e', 1)]),
+            ('e', vec![('d', 5)]),
+        ]);
+        let successors = |node: &char| graph.get(node).cloned().unwrap_or_default();
+        let search = dijkstra('a', successors, |&node| node == 'd');
+        assert_eq!(search.cost(), Some(3));
+        let mut predecessors = search.predecessors(&'d').to_vec();
+        predecessors.sort();
+        assert_eq!(predecessors, vec!['b', 'c']);
+        assert_eq!(search.all_paths(&'d').len(), 2);
+        assert_eq!(search.on_best_paths(), HashSet::from(['a', 'b', 'c', 'd']));
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = Grid::new_with(20, 20, '.');
+        let end = Coordinate::new(19, 19);
+        let manhattan =
+            |c: &Coordinate| (end.y - c.y).unsigned_abs() + (end.x - c.x).unsigned_abs();
+        let successors = |c: &Coordinate| grid.neighbors4(c).map(|n| (n, 1)).collect::<Vec<_>>();
+        let search = astar(Coordinate::new(0, 0), successors, manhattan, |c| *c == end);
+        assert_eq!(search.cost(), Some(38));
+        assert_eq!(search.path(&end).unwrap().len(), 39);
+        // Only states that could still be on a cheapest path get expanded
+        let dijkstra = dijkstra(Coordinate::new(0, 0), successors, |c| *c == end);
+        assert_eq!(dijkstra.cost(), Some(38));
+        assert!(search.distances().len() <= dijkstra.distances().len());
+    }
+}
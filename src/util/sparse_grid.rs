@@ -0,0 +1,254 @@
+use crate::util::coordinate::Coordinate;
+use crate::util::dir::Direction;
+use crate::util::grid::Grid;
+use crate::util::rect::Rect;
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+
+/// A grid without bounds, storing only the coordinates that hold a value. Coordinates can be negative, and the
+/// bounding box grows as values are added.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Coordinate, T>,
+    bounds: Option<Rect>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest rectangle containing every value, `None` while the grid is empty.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// Whether `coordinate` holds a value.
+    pub fn contains(&self, coordinate: &Coordinate) -> bool {
+        self.cells.contains_key(coordinate)
+    }
+
+    pub fn get(&self, coordinate: &Coordinate) -> Option<&T> {
+        self.cells.get(coordinate)
+    }
+
+    pub fn get_mut(&mut self, coordinate: &Coordinate) -> Option<&mut T> {
+        self.cells.get_mut(coordinate)
+    }
+
+    /// Stores `value` at `coordinate`, returning the value it replaced.
+    pub fn replace(&mut self, coordinate: &Coordinate, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.expanded_to(coordinate),
+            None => Rect::new(*coordinate, *coordinate),
+        });
+        self.cells.insert(*coordinate, value)
+    }
+
+    pub fn remove(&mut self, coordinate: &Coordinate) -> Option<T> {
+        let value = self.cells.remove(coordinate)?;
+        // Only a value on the edge of the bounding box can shrink it
+        let on_edge = self.bounds.is_some_and(|b| {
+            [b.min.y, b.max.y].contains(&coordinate.y) || [b.min.x, b.max.x].contains(&coordinate.x)
+        });
+        if on_edge {
+            self.bounds = Rect::bounding(self.cells.keys().copied());
+        }
+        Some(value)
+    }
+
+    /// Every coordinate with its value, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.cells
+            .iter()
+            .map(|(coordinate, value)| (*coordinate, value))
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// The coordinates above, right of, below and left of `coordinate` that hold a value.
+    pub fn neighbors4(&self, coordinate: &Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.neighbors(coordinate, &Direction::CARDINAL)
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// The surrounding eight coordinates that hold a value, clockwise from the one above.
+    pub fn neighbors8(&self, coordinate: &Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        self.neighbors(coordinate, &Direction::ALL)
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    pub fn neighbors4_values(
+        &self,
+        coordinate: &Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        self.neighbors(coordinate, &Direction::CARDINAL)
+    }
+
+    pub fn neighbors8_values(
+        &self,
+        coordinate: &Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        self.neighbors(coordinate, &Direction::ALL)
+    }
+
+    fn neighbors(
+        &self,
+        coordinate: &Coordinate,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Coordinate, &T)> + '_ {
+        let center = *coordinate;
+        directions.iter().filter_map(move |dir| {
+            let neighbor = center.offset(dir);
+            self.get(&neighbor).map(|value| (neighbor, value))
+        })
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// A dense grid covering the bounding box, with `fill` where there's no value, and the offset to subtract
+    /// from a coordinate of this grid to get the dense grid's coordinate.
+    pub fn to_grid(&self, fill: T) -> (Grid<T>, Coordinate) {
+        let Some(bounds) = self.bounds else {
+            return (Grid::new(Vec::new()), Coordinate::new(0, 0));
+        };
+        let rows = (bounds.min.y..=bounds.max.y)
+            .map(|y| {
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| self.get(&Coordinate::new(y, x)).unwrap_or(&fill).clone())
+                    .collect()
+            })
+            .collect();
+        (Grid::new(rows), bounds.min)
+    }
+}
+
+impl<T: Display> SparseGrid<T> {
+    /// Draws the bounding box row by row, with `empty` where there's no value.
+    pub fn render(&self, empty: char) -> String {
+        let mut rendered = String::new();
+        if let Some(bounds) = self.bounds {
+            for y in bounds.min.y..=bounds.max.y {
+                for x in bounds.min.x..=bounds.max.x {
+                    let _ = match self.get(&Coordinate::new(y, x)) {
+                        Some(value) => write!(rendered, "{}", value),
+                        None => write!(rendered, "{}", empty),
+                    };
+                }
+                rendered.push('\n');
+            }
+        }
+        rendered
+    }
+}
+
+impl<T> Extend<(Coordinate, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Coordinate, T)>>(&mut self, iter: I) {
+        for (coordinate, value) in iter {
+            self.replace(&coordinate, value);
+        }
+    }
+}
+
+impl<T> FromIterator<(Coordinate, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Coordinate, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_and_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.replace(&Coordinate::new(-2, 3), 'a'), None);
+        assert_eq!(grid.replace(&Coordinate::new(1, -1), 'b'), None);
+        assert_eq!(grid.replace(&Coordinate::new(1, -1), 'c'), Some('b'));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(Coordinate::new(-2, -1), Coordinate::new(1, 3)))
+        );
+        assert_eq!(grid.get(&Coordinate::new(1, -1)), Some(&'c'));
+        assert_eq!(grid.get(&Coordinate::new(0, 0)), None);
+    }
+
+    #[test]
+    fn test_remove_shrinks_bounds() {
+        let mut grid: SparseGrid<u8> = [(0, 0), (5, 5), (2, 2)]
+            .into_iter()
+            .map(|(y, x)| (Coordinate::new(y, x), 1))
+            .collect();
+        assert_eq!(grid.remove(&Coordinate::new(5, 5)), Some(1));
+        assert_eq!(
+            grid.bounds(),
+            Some(Rect::new(Coordinate::new(0, 0), Coordinate::new(2, 2)))
+        );
+        assert_eq!(grid.remove(&Coordinate::new(5, 5)), None);
+        grid.remove(&Coordinate::new(0, 0));
+        grid.remove(&Coordinate::new(2, 2));
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: SparseGrid<char> = [((0, 0), 'a'), ((-1, 0), 'b'), ((1, 1), 'c')]
+            .into_iter()
+            .map(|((y, x), v)| (Coordinate::new(y, x), v))
+            .collect();
+        let center = Coordinate::new(0, 0);
+        assert_eq!(
+            grid.neighbors4(&center).collect::<Vec<_>>(),
+            vec![Coordinate::new(-1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors8_values(&center)
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec!['b', 'c']
+        );
+    }
+
+    #[test]
+    fn test_render_and_to_grid() {
+        let grid: SparseGrid<char> = [((-1, -1), '#'), ((0, 1), '#')]
+            .into_iter()
+            .map(|((y, x), v)| (Coordinate::new(y, x), v))
+            .collect();
+        assert_eq!(grid.render('.'), "#..\n..#\n");
+        let (dense, offset) = grid.to_grid('.');
+        assert_eq!(offset, Coordinate::new(-1, -1));
+        assert_eq!(dense.to_string(), "#..\n..#\n");
+        assert_eq!(dense.get(&(Coordinate::new(0, 1) - offset)), Some(&'#'));
+        assert_eq!(SparseGrid::<char>::new().render('.'), "");
+        assert!(SparseGrid::<char>::new().to_grid('.').0.is_empty());
+    }
+}
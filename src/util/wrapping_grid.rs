@@ -0,0 +1,150 @@
+use crate::util::coordinate::Coordinate;
+use crate::util::dir::Direction;
+use crate::util::grid::Grid;
+use std::ops::{Index, IndexMut};
+
+/// A [`Grid`] on a torus: leaving one edge enters at the opposite one, so every coordinate addresses a cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrappingGrid<T> {
+    grid: Grid<T>,
+}
+
+impl<T> WrappingGrid<T> {
+    /// Panics if `grid` has no cells, as there's nothing to wrap around to.
+    pub fn new(grid: Grid<T>) -> Self {
+        assert!(!grid.is_empty(), "can't wrap around an empty grid");
+        Self { grid }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// The coordinate within the grid that `coordinate` wraps around to.
+    pub fn wrap(&self, coordinate: &Coordinate) -> Coordinate {
+        Coordinate::new(
+            coordinate.y.rem_euclid(self.grid.height() as isize),
+            coordinate.x.rem_euclid(self.grid.width() as isize),
+        )
+    }
+
+    /// Where moving `times` steps in `dir` from `coordinate` ends up.
+    pub fn offset(&self, coordinate: &Coordinate, dir: &Direction, times: isize) -> Coordinate {
+        self.wrap(&Coordinate::new(
+            coordinate.y + dir.dy * times,
+            coordinate.x + dir.dx * times,
+        ))
+    }
+
+    /// The four neighbours of `coordinate`, wrapped around the edges. In a grid one cell wide or high, some
+    /// of them are the same cell.
+    pub fn neighbors4(&self, coordinate: &Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        let center = *coordinate;
+        Direction::CARDINAL
+            .iter()
+            .map(move |dir| self.offset(&center, dir, 1))
+    }
+
+    /// The eight surrounding cells of `coordinate`, wrapped around the edges, clockwise from the one above.
+    pub fn neighbors8(&self, coordinate: &Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        let center = *coordinate;
+        Direction::ALL
+            .iter()
+            .map(move |dir| self.offset(&center, dir, 1))
+    }
+
+    pub fn get(&self, coordinate: &Coordinate) -> &T {
+        &self[*coordinate]
+    }
+
+    pub fn get_mut(&mut self, coordinate: &Coordinate) -> &mut T {
+        &mut self[*coordinate]
+    }
+
+    /// Stores `value` at the cell `coordinate` wraps around to, returning the value it replaced.
+    pub fn replace(&mut self, coordinate: &Coordinate, value: T) -> T {
+        std::mem::replace(self.get_mut(coordinate), value)
+    }
+}
+
+impl<T> Index<Coordinate> for WrappingGrid<T> {
+    type Output = T;
+
+    fn index(&self, coordinate: Coordinate) -> &T {
+        &self.grid[self.wrap(&coordinate)]
+    }
+}
+
+impl<T> IndexMut<Coordinate> for WrappingGrid<T> {
+    fn index_mut(&mut self, coordinate: Coordinate) -> &mut T {
+        let wrapped = self.wrap(&coordinate);
+        &mut self.grid[wrapped]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> WrappingGrid<u32> {
+        WrappingGrid::new("123\n456".parse().unwrap())
+    }
+
+    #[test]
+    fn test_wrap() {
+        let grid = grid();
+        assert_eq!(grid.wrap(&Coordinate::new(1, 2)), Coordinate::new(1, 2));
+        assert_eq!(grid.wrap(&Coordinate::new(2, 3)), Coordinate::new(0, 0));
+        assert_eq!(grid.wrap(&Coordinate::new(-1, -1)), Coordinate::new(1, 2));
+        assert_eq!(grid.wrap(&Coordinate::new(-7, 13)), Coordinate::new(1, 1));
+        assert_eq!(grid[Coordinate::new(-1, 0)], 4);
+        assert_eq!(*grid.get(&Coordinate::new(4, 5)), 3);
+    }
+
+    #[test]
+    fn test_offset() {
+        let grid = grid();
+        let start = Coordinate::new(0, 1);
+        assert_eq!(
+            grid.offset(&start, &Direction::new(1, -2), 5),
+            Coordinate::new(1, 0)
+        );
+        assert_eq!(
+            grid.offset(&start, &Direction::LEFT, 2),
+            Coordinate::new(0, 2)
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors4(&Coordinate::new(0, 0)).collect::<Vec<_>>(),
+            vec![
+                Coordinate::new(1, 0),
+                Coordinate::new(0, 1),
+                Coordinate::new(1, 0),
+                Coordinate::new(0, 2),
+            ]
+        );
+        assert_eq!(grid.neighbors8(&Coordinate::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut grid = grid();
+        assert_eq!(grid.replace(&Coordinate::new(2, -1), 9), 3);
+        grid[Coordinate::new(-2, 3)] += 1;
+        assert_eq!(grid.into_grid().to_string(), "229\n456\n");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty() {
+        WrappingGrid::new(Grid::<u32>::new(vec![]));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::answer::Answer;
+use crate::error;
+use crate::runner;
+use crate::solution::Entry;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    /// Parses the `Part 1: <answer>` / `Part 2: <answer>` lines printed by the CLI.
+    pub fn parse(s: &str) -> Expected {
+        let mut expected = Expected::default();
+        for line in s.lines() {
+            if let Some(answer) = line.strip_prefix("Part 1: ") {
+                expected.part1 = Some(answer.trim().to_string());
+            } else if let Some(answer) = line.strip_prefix("Part 2: ") {
+                expected.part2 = Some(answer.trim().to_string());
+            }
+        }
+        expected
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub part: u8,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verification {
+    Passed,
+    Failed(Vec<Mismatch>),
+    MissingInput,
+    MissingAnswers,
+}
+
+pub fn answers_path(year: u16, day: u8) -> PathBuf {
+    runner::input_path(year, day).with_extension("ans")
+}
+
+pub fn verify(entry: &Entry) -> io::Result<Verification> {
+    verify_files(
+        entry,
+        runner::input_path(entry.year, entry.day),
+        answers_path(entry.year, entry.day),
+    )
+}
+
+/// An empty input, like the placeholder `new` writes before the input is fetched, counts as missing.
+fn verify_files(entry: &Entry, input: PathBuf, answers: PathBuf) -> io::Result<Verification> {
+    let input = match read_if_exists(input)? {
+        Some(input) if !input.trim().is_empty() => input,
+        _ => return Ok(Verification::MissingInput),
+    };
+    let expected = match read_if_exists(answers)? {
+        Some(answers) => Expected::parse(&answers),
+        None => return Ok(Verification::MissingAnswers),
+    };
+    Ok(check(entry, &input, &expected))
+}
+
+pub fn check(entry: &Entry, input: &str, expected: &Expected) -> Verification {
+    let mut mismatches = Vec::new();
+    if let Some(expected) = &expected.part1 {
+        let actual = runner::catch_panic(|| entry.solution.part1(input));
+        mismatches.extend(compare(1, expected, actual));
+    }
+    if let Some(expected) = &expected.part2 {
+        let actual = runner::catch_panic(|| entry.solution.part2(input));
+        mismatches.extend(compare(2, expected, actual));
+    }
+    match mismatches.is_empty() {
+        true => Verification::Passed,
+        false => Verification::Failed(mismatches),
+    }
+}
+
+fn compare(part: u8, expected: &str, actual: error::Result<Answer>) -> Option<Mismatch> {
+    let actual = match actual {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {}", e),
+    };
+    match actual == expected {
+        true => None,
+        false => Some(Mismatch {
+            part,
+            expected: expected.to_string(),
+            actual,
+        }),
+    }
+}
+
+fn read_if_exists(path: PathBuf) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    #[test]
+    fn test_parse_expected() {
+        let expected = Expected::parse("Part 1: 138\nPart 2: 1771\n");
+        assert_eq!(expected.part1, Some("138".to_string()));
+        assert_eq!(expected.part2, Some("1771".to_string()));
+        assert_eq!(Expected::parse("Part 2: ab,cd").part1, None);
+    }
+
+    #[test]
+    fn test_answers_path() {
+        assert_eq!(answers_path(2023, 19), PathBuf::from("inputs/2023/19.ans"));
+    }
+
+    #[test]
+    fn test_check() {
+        let entry = solution::find(2015, 1).unwrap();
+        let expected = Expected {
+            part1: Some("-1".to_string()),
+            part2: Some("4".to_string()),
+        };
+        assert_eq!(
+            check(entry, "()())", &expected),
+            Verification::Failed(vec![Mismatch {
+                part: 2,
+                expected: "4".to_string(),
+                actual: "5".to_string(),
+            }])
+        );
+        assert_eq!(
+            check(entry, "()())", &Expected::default()),
+            Verification::Passed
+        );
+    }
+
+    #[test]
+    fn test_verify_empty_input() {
+        let entry = solution::find(2015, 1).unwrap();
+        let path = std::env::temp_dir().join("aoc-rust-verify-empty.in");
+        let missing = std::env::temp_dir().join("aoc-rust-verify-missing.ans");
+        fs::write(&path, " \n").unwrap();
+        let verification = verify_files(entry, path.clone(), missing.clone());
+        assert_eq!(verification.unwrap(), Verification::MissingInput);
+        fs::write(&path, "()())").unwrap();
+        let verification = verify_files(entry, path.clone(), missing);
+        assert_eq!(verification.unwrap(), Verification::MissingAnswers);
+        fs::remove_file(path).unwrap();
+    }
+}
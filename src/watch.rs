@@ -0,0 +1,194 @@
+use crate::error::{Error, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Polls files for modifications by comparing their modification times.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    /// Modification time per path as of the last poll, `None` before the first one
+    modified: Option<Vec<Option<SystemTime>>>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        Watcher {
+            paths,
+            modified: None,
+        }
+    }
+
+    /// The watched files whose modification time changed since the last call, including files that
+    /// appeared or disappeared. Every file counts as changed on the first call.
+    pub fn changed(&mut self) -> Vec<&PathBuf> {
+        let modified = self
+            .paths
+            .iter()
+            .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect::<Vec<_>>();
+        let changed = match &self.modified {
+            Some(last) => self
+                .paths
+                .iter()
+                .zip(last.iter().zip(&modified))
+                .filter(|(_, (last, modified))| last != modified)
+                .map(|(path, _)| path)
+                .collect(),
+            None => self.paths.iter().collect(),
+        };
+        self.modified = Some(modified);
+        changed
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    /// The answer, or the error message
+    pub answer: std::result::Result<String, String>,
+    pub solve: Duration,
+}
+
+/// Reads back the rows written by [`crate::output::to_csv`].
+pub fn parse_csv(csv: &str) -> Result<Vec<PartResult>> {
+    records(csv)
+        .into_iter()
+        .skip(1)
+        .map(|record| match &record[..] {
+            [_, _, part, status, answer, error, _, solve, ..] => Ok(PartResult {
+                part: part.parse()?,
+                answer: match status.as_str() {
+                    "ok" => Ok(answer.clone()),
+                    _ => Err(error.clone()),
+                },
+                solve: Duration::from_nanos(solve.parse()?),
+            }),
+            _ => Err(Error::parse("expected at least 8 columns")),
+        })
+        .collect()
+}
+
+/// Splits CSV into records of fields, honouring quoted fields with `""` escapes and line breaks.
+fn records(csv: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// One line per part, noting how each answer compares with the previous run's.
+pub fn diff(previous: &[PartResult], current: &[PartResult]) -> Vec<String> {
+    current
+        .iter()
+        .map(|result| {
+            let before = previous.iter().find(|p| p.part == result.part);
+            let answer = match &result.answer {
+                Ok(answer) => answer.clone(),
+                Err(e) => format!("error: {}", e),
+            };
+            let change = match before {
+                None => String::new(),
+                Some(before) if before.answer == result.answer => " (unchanged)".to_string(),
+                Some(before) => match &before.answer {
+                    Ok(answer) => format!(" (was {})", answer),
+                    Err(_) => " (was an error)".to_string(),
+                },
+            };
+            format!(
+                "Part {}: {}{}  [{:.2?}]",
+                result.part, answer, change, result.solve
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    fn result(part: u8, answer: std::result::Result<&str, &str>, millis: u64) -> PartResult {
+        PartResult {
+            part,
+            answer: answer.map(str::to_string).map_err(str::to_string),
+            solve: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir().join("aoc-rust-watch.rs");
+        fs::write(&path, "").unwrap();
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert_eq!(watcher.changed(), vec![&path]);
+        assert!(watcher.changed().is_empty());
+        let later = SystemTime::now() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(watcher.changed(), vec![&path]);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![&path]);
+        assert!(watcher.changed().is_empty());
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "year,day,part,status,answer,error,parse_ns,solve_ns\n\
+                   2024,18,1,ok,22,,100,2000000\n\
+                   2024,18,2,error,,\"failed to parse line 3: missing \"\",\"\"\",,500\n";
+        assert_eq!(
+            parse_csv(csv).unwrap(),
+            vec![
+                result(1, Ok("22"), 2),
+                PartResult {
+                    part: 2,
+                    answer: Err("failed to parse line 3: missing \",\"".to_string()),
+                    solve: Duration::from_nanos(500),
+                },
+            ]
+        );
+        assert!(parse_csv("header\n1,2\n").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = vec![result(1, Ok("41"), 5), result(2, Ok("7"), 5)];
+        let current = vec![result(1, Ok("42"), 3), result(2, Ok("7"), 5)];
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                "Part 1: 42 (was 41)  [3.00ms]",
+                "Part 2: 7 (unchanged)  [5.00ms]"
+            ]
+        );
+        assert_eq!(
+            diff(&[], &[result(1, Err("boom"), 1)]),
+            vec!["Part 1: error: boom  [1.00ms]"]
+        );
+    }
+}
@@ -1,7 +1,4 @@
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2);
 
 fn part1(input: &str) -> i32 {
     input.chars().fold(0, |sum, c| match c {
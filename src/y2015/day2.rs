@@ -1,36 +1,29 @@
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+use crate::error::{parse_lines, Error, Result};
+
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> i32 {
-    input
-        .lines()
-        .map(|line| line.split('x'))
-        .map(|mut parts| {
-            (
-                parts.next().unwrap().parse::<i32>().unwrap(),
-                parts.next().unwrap().parse::<i32>().unwrap(),
-                parts.next().unwrap().parse::<i32>().unwrap(),
-            )
-        })
+fn part1(input: &str) -> Result<i32> {
+    Ok(parse(input)?
+        .into_iter()
         .map(|(l, w, h)| packaging(w, l, h))
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> i32 {
-    input
-        .lines()
-        .map(|line| line.split('x'))
-        .map(|mut parts| {
-            (
-                parts.next().unwrap().parse::<i32>().unwrap(),
-                parts.next().unwrap().parse::<i32>().unwrap(),
-                parts.next().unwrap().parse::<i32>().unwrap(),
-            )
-        })
+fn part2(input: &str) -> Result<i32> {
+    Ok(parse(input)?
+        .into_iter()
         .map(|(l, w, h)| ribbon(l, w, h))
-        .sum()
+        .sum())
+}
+
+fn parse(input: &str) -> Result<Vec<(i32, i32, i32)>> {
+    parse_lines(input, |line| -> Result<(i32, i32, i32)> {
+        let mut parts = line.split('x');
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(l), Some(w), Some(h), None) => Ok((l.parse()?, w.parse()?, h.parse()?)),
+            _ => Err(Error::parse(format!("invalid dimensions '{}'", line))),
+        }
+    })
 }
 
 fn packaging(l: i32, w: i32, h: i32) -> i32 {
@@ -64,4 +57,13 @@ mod tests {
         assert_eq!(34, ribbon(2, 3, 4));
         assert_eq!(14, ribbon(1, 1, 10));
     }
+
+    #[test]
+    fn invalid_dimensions() {
+        let error = part1("2x3x4\n1x1").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse line 2: invalid dimensions '1x1'"
+        );
+    }
 }
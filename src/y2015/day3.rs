@@ -1,39 +1,49 @@
-use std::collections::HashSet;
+use crate::util::coordinate::Coordinate;
+use crate::util::dir::Direction;
+use crate::util::sparse_grid::SparseGrid;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2);
 
 fn part2(input: &str) -> u32 {
     let (santa, robot): (Vec<_>, Vec<_>) =
         input.chars().enumerate().partition(|(i, _c)| i % 2 == 0);
-    deliver_presents(santa.into_iter().map(|(_i, c)| c))
-        .union(&deliver_presents(robot.into_iter().map(|(_i, c)| c)))
-        .count() as u32
+    let santa = deliver_presents(santa.into_iter().map(|(_i, c)| c));
+    let robot = deliver_presents(robot.into_iter().map(|(_i, c)| c));
+    let robot_only = robot
+        .coordinates()
+        .filter(|house| !santa.contains(house))
+        .count();
+    (santa.len() + robot_only) as u32
 }
 
 fn part1(input: &str) -> u32 {
     deliver_presents(input.chars()).len() as u32
 }
 
-fn deliver_presents<I>(instructions: I) -> HashSet<(i32, i32)>
+/// The number of presents delivered to each visited house.
+fn deliver_presents<I>(instructions: I) -> SparseGrid<u32>
 where
     I: Iterator<Item = char>,
 {
-    let mut visited = vec![(0, 0)].into_iter().collect::<HashSet<(i32, i32)>>();
-    let mut pos = (0, 0);
+    let mut pos = Coordinate::new(0, 0);
+    let mut houses = SparseGrid::from_iter([(pos, 1)]);
     for c in instructions {
-        match c {
-            '^' => pos = (pos.0, pos.1 + 1),
-            'v' => pos = (pos.0, pos.1 - 1),
-            '>' => pos = (pos.0 + 1, pos.1),
-            '<' => pos = (pos.0 - 1, pos.1),
-            _ => {}
+        let dir = match c {
+            '^' => Direction::UP,
+            'v' => Direction::DOWN,
+            '>' => Direction::RIGHT,
+            '<' => Direction::LEFT,
+            _ => continue,
+        };
+        pos = pos.offset(&dir);
+        match houses.get_mut(&pos) {
+            Some(presents) => *presents += 1,
+            None => {
+                houses.replace(&pos, 1);
+            }
         }
-        visited.insert(pos);
     }
-    visited
+    houses
 }
 
 #[cfg(test)]
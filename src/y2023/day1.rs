@@ -12,10 +12,7 @@ const DIGITS: [(&str, u32); 9] = [
     ("nine", 9),
 ];
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2);
 
 fn part1(input: &str) -> u32 {
     input
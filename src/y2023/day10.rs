@@ -1,24 +1,22 @@
 use std::collections::HashSet;
 
+use crate::error::{Error, Result};
+
 const VALID_NORTH: [char; 3] = ['|', '7', 'F'];
 const VALID_SOUTH: [char; 3] = ['|', 'L', 'J'];
 const VALID_WEST: [char; 3] = ['-', 'L', 'F'];
 const VALID_EAST: [char; 3] = ['-', 'J', '7'];
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse_tiles);
 
-fn part1(input: &str) -> usize {
-    let (tiles, origin) = parse_tiles(input);
-    find_path(&tiles, origin).unwrap().len() / 2
+fn part1(input: &str) -> Result<usize> {
+    let (tiles, origin) = parse_tiles(input)?;
+    Ok(find_loop(&tiles, origin)?.len() / 2)
 }
 
-fn part2(input: &str) -> i64 {
-    let (mut tiles, origin) = parse_tiles(input);
-    let path = find_path(&tiles, origin)
-        .unwrap()
+fn part2(input: &str) -> Result<i64> {
+    let (mut tiles, origin) = parse_tiles(input)?;
+    let path = find_loop(&tiles, origin)?
         .into_iter()
         .collect::<HashSet<_>>();
     for (y, row) in tiles.iter_mut().enumerate() {
@@ -52,12 +50,16 @@ fn part2(input: &str) -> i64 {
             inside = !inside;
         }
     }
-    num_encompassing
+    Ok(num_encompassing)
+}
+
+fn find_loop(map: &[Vec<char>], origin: (usize, usize)) -> Result<Vec<(usize, usize)>> {
+    find_path(map, origin).ok_or_else(|| Error::NoAnswer("no loop through the start".to_string()))
 }
 
 fn find_path(map: &[Vec<char>], origin: (usize, usize)) -> Option<Vec<(usize, usize)>> {
     let first = match map[origin.0][origin.1] {
-        '|' | 'L' | 'J' => Some((origin.0 - 1, origin.1)),
+        '|' | 'L' | 'J' => Some((origin.0.checked_sub(1)?, origin.1)),
         '7' | 'F' => Some((origin.0 + 1, origin.1)),
         '-' => Some((origin.0, origin.1 + 1)),
         _ => None,
@@ -77,15 +79,18 @@ fn next_pipe(map: &[Vec<char>], prev: &[(usize, usize)]) -> Option<(usize, usize
     let xv = (prev[1].1 as isize) - (prev[0].1 as isize);
     let pipe = map.get(prev[1].0)?.get(prev[1].1)?;
     match pipe {
-        '|' | 'L' | 'J' if (yv < 0 || xv != 0) => Some((prev[1].0 - 1, prev[1].1)),
+        '|' | 'L' | 'J' if (yv < 0 || xv != 0) => Some((prev[1].0.checked_sub(1)?, prev[1].1)),
         '|' | 'F' | '7' if (yv > 0 || xv != 0) => Some((prev[1].0 + 1, prev[1].1)),
-        '-' | '7' | 'J' if (xv < 0 || yv != 0) => Some((prev[1].0, prev[1].1 - 1)),
+        '-' | '7' | 'J' if (xv < 0 || yv != 0) => Some((prev[1].0, prev[1].1.checked_sub(1)?)),
         '-' | 'L' | 'F' if (xv > 0 || yv != 0) => Some((prev[1].0, prev[1].1 + 1)),
         _ => None,
     }
 }
 
-fn parse_tiles(input: &str) -> (Vec<Vec<char>>, (usize, usize)) {
+/// The pipe map, with the start tile replaced by the pipe it stands for.
+type Tiles = Vec<Vec<char>>;
+
+fn parse_tiles(input: &str) -> Result<(Tiles, (usize, usize))> {
     let mut map: Vec<_> = input
         .lines()
         .map(|line| line.chars().collect::<Vec<_>>())
@@ -95,12 +100,12 @@ fn parse_tiles(input: &str) -> (Vec<Vec<char>>, (usize, usize)) {
         .enumerate()
         .filter_map(|(y, row)| row.iter().position(|e| *e == 'S').map(|x| (y, x)))
         .next()
-        .unwrap();
-    map[origin.0][origin.1] = start_pipe(&map, &origin);
-    (map, origin)
+        .ok_or_else(|| Error::parse("no start tile 'S'"))?;
+    map[origin.0][origin.1] = start_pipe(&map, &origin)?;
+    Ok((map, origin))
 }
 
-fn start_pipe(map: &[Vec<char>], origin: &(usize, usize)) -> char {
+fn start_pipe(map: &[Vec<char>], origin: &(usize, usize)) -> Result<char> {
     let nexts = vec![(-1, 0), (1, 0), (0, -1), (0, 1)]
         .into_iter()
         .filter_map(|(yv, xv)| {
@@ -123,13 +128,15 @@ fn start_pipe(map: &[Vec<char>], origin: &(usize, usize)) -> char {
         .map(|(_, v)| v)
         .collect::<Vec<_>>();
     match nexts.as_slice() {
-        [(-1, 0), (1, 0)] => '|',
-        [(0, -1), (0, 1)] => '-',
-        [(-1, 0), (0, 1)] => 'L',
-        [(-1, 0), (0, -1)] => 'J',
-        [(1, 0), (0, -1)] => '7',
-        [(1, 0), (0, 1)] => 'F',
-        _ => panic!("No start pipe found"),
+        [(-1, 0), (1, 0)] => Ok('|'),
+        [(0, -1), (0, 1)] => Ok('-'),
+        [(-1, 0), (0, 1)] => Ok('L'),
+        [(-1, 0), (0, -1)] => Ok('J'),
+        [(1, 0), (0, -1)] => Ok('7'),
+        [(1, 0), (0, 1)] => Ok('F'),
+        _ => Err(Error::parse(
+            "the start tile doesn't connect to exactly two pipes",
+        )),
     }
 }
 
@@ -144,7 +151,7 @@ mod tests {
 L|7||
 -L-J|
 L|-JF";
-        assert_eq!(part1(input), 4);
+        assert_eq!(part1(input).unwrap(), 4);
     }
 
     #[test]
@@ -154,7 +161,7 @@ L|-JF";
 SJLL7
 |F--J
 LJ.LJ";
-        assert_eq!(part1(input), 8);
+        assert_eq!(part1(input).unwrap(), 8);
     }
 
     #[test]
@@ -168,7 +175,7 @@ LJ.LJ";
 .|..|.|..|.
 .L--J.L--J.
 ...........";
-        assert_eq!(part2(input), 4);
+        assert_eq!(part2(input).unwrap(), 4);
     }
 
     #[test]
@@ -183,7 +190,7 @@ L--J.L7...LJS7F-7L7.
 .....|FJLJ|FJ|F7|.LJ
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...";
-        assert_eq!(part2(input), 8);
+        assert_eq!(part2(input).unwrap(), 8);
     }
 
     #[test]
@@ -198,6 +205,6 @@ L---JF-JLJIIIIFJLJJ7
 7-L-JL7||F7|L7F-7F7|
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L";
-        assert_eq!(part2(input), 10);
+        assert_eq!(part2(input).unwrap(), 10);
     }
 }
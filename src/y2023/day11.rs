@@ -1,90 +1,62 @@
+use crate::error::Result;
+use crate::params::at_least;
+use crate::util::coordinate::Coordinate;
+use crate::util::grid::Grid;
 use itertools::Itertools;
+use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input, 1000000));
-}
+// Part 1 always expands empty rows and columns twice, only part 2 uses `multiplier`
+crate::params!(
+    struct Params { multiplier: usize = 1000000 }
+);
+crate::solution!(part1, part2, Grid::<char>::from_str; Params);
 
-fn part1(input: &str) -> usize {
+fn part1(input: &str, _: &Params) -> Result<usize> {
     sum_distances(input, 2)
 }
 
-fn part2(input: &str, multiplier: usize) -> usize {
+fn part2(input: &str, params: &Params) -> Result<usize> {
+    let multiplier = at_least("multiplier", params.multiplier, 1)?;
     sum_distances(input, multiplier)
 }
 
-fn sum_distances(input: &str, multiplier: usize) -> usize {
-    let universe = parse(input);
-    let expansion_points = expansion_points(&universe);
-    planet_positions(&universe)
-        .into_iter()
+fn sum_distances(input: &str, multiplier: usize) -> Result<usize> {
+    let universe = Grid::<char>::from_str(input)?;
+    let expansion_points = (
+        universe.empty_rows(|tile| *tile != '#'),
+        universe.empty_columns(|tile| *tile != '#'),
+    );
+    Ok(universe
+        .find_all(&'#')
         .combinations(2)
         .map(|pair| distance(pair[0], pair[1], &expansion_points, multiplier))
-        .sum()
+        .sum())
 }
 
 fn distance(
-    a: (usize, usize),
-    b: (usize, usize),
+    a: Coordinate,
+    b: Coordinate,
     expansion_points: &(Vec<usize>, Vec<usize>),
     multiplier: usize,
 ) -> usize {
-    let mut dist = a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+    let mut dist = a.y.abs_diff(b.y) + a.x.abs_diff(b.x);
     for y in &expansion_points.0 {
-        if *y > a.0.min(b.0) && *y < a.0.max(b.0) {
+        if (*y as isize) > a.y.min(b.y) && (*y as isize) < a.y.max(b.y) {
             dist += multiplier - 1;
         }
     }
     for x in &expansion_points.1 {
-        if *x > a.1.min(b.1) && *x < a.1.max(b.1) {
+        if (*x as isize) > a.x.min(b.x) && (*x as isize) < a.x.max(b.x) {
             dist += multiplier - 1;
         }
     }
     dist
 }
 
-fn planet_positions(universe: &[Vec<char>]) -> Vec<(usize, usize)> {
-    universe
-        .iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .filter(|(_, tile)| **tile == '#')
-                .map(move |(x, _)| (y, x))
-        })
-        .collect()
-}
-
-fn expansion_points(universe: &[Vec<char>]) -> (Vec<usize>, Vec<usize>) {
-    let mut rows = Vec::<usize>::new();
-    for (y, row) in universe.iter().enumerate() {
-        if row.iter().all(|tile| *tile != '#') {
-            rows.push(y);
-        }
-    }
-    let mut columns = Vec::<usize>::new();
-    for x in 0..universe[0].len() {
-        if (0..universe.len())
-            .map(|y| (y, universe[y][x]))
-            .all(|(_, tile)| tile != '#')
-        {
-            columns.push(x);
-        }
-    }
-    (rows, columns)
-}
-
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Error;
 
     #[test]
     fn part1_test() {
@@ -98,7 +70,7 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!(part1(input), 374);
+        assert_eq!(part1(input, &Params::default()).unwrap(), 374);
     }
 
     #[test]
@@ -113,7 +85,11 @@ mod tests {
 ..........
 .......#..
 #...#.....";
-        assert_eq!(part2(input, 10), 1030);
-        assert_eq!(part2(input, 100), 8410);
+        assert_eq!(part2(input, &Params { multiplier: 10 }).unwrap(), 1030);
+        assert_eq!(part2(input, &Params { multiplier: 100 }).unwrap(), 8410);
+        assert!(matches!(
+            part2(input, &Params { multiplier: 0 }),
+            Err(Error::Usage(_))
+        ));
     }
 }
@@ -1,100 +1,75 @@
+use crate::error::Result;
+use crate::util::coordinate::Coordinate;
+use crate::util::grid::Grid;
 use std::{
     collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
+    str::FromStr,
 };
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, Grid::<char>::from_str);
 
-fn part1(input: &str) -> u64 {
-    let mut map = parse(input);
+fn part1(input: &str) -> Result<u64> {
+    let mut map = Grid::<char>::from_str(input)?;
     tilt(&mut map);
-    total_load(&map)
+    Ok(total_load(&map))
 }
 
-fn part2(input: &str) -> u64 {
-    let mut map = parse(input);
+fn part2(input: &str) -> Result<u64> {
+    let mut map = Grid::<char>::from_str(input)?;
     let mut hashes: HashMap<u64, isize> = HashMap::new();
-    let mut cycle = None;
-    for i in 0.. {
-        if let Some(prev) = hashes.insert(calculate_hash(&map), i) {
-            cycle = Some((prev, i));
-            break;
+    let mut cycle_end = 0;
+    let cycle_start = loop {
+        if let Some(prev) = hashes.insert(calculate_hash(&map), cycle_end) {
+            break prev;
         };
-        map = spin_cycle(map);
-    }
-    let (cycle_start, cycle_end) = cycle.unwrap();
+        spin_cycle(&mut map);
+        cycle_end += 1;
+    };
     let cycle_length = cycle_end - cycle_start;
     for _ in 0..((1000000000 - cycle_start) % cycle_length) {
-        map = spin_cycle(map);
+        spin_cycle(&mut map);
     }
-    total_load(&map)
+    Ok(total_load(&map))
 }
 
-fn tilt(map: &mut [Vec<char>]) {
-    for x in 0..map[0].len() {
-        for y in 0..map.len() {
-            if map[y][x] != 'O' {
-                continue;
-            }
-            if let Some((new_y, new_x)) = new_position(y, x, map) {
-                map[y][x] = '.';
-                map[new_y][new_x] = 'O';
+/// Rolls every rock as far north as it goes.
+fn tilt(map: &mut Grid<char>) {
+    for x in 0..map.width() as isize {
+        let mut free = 0;
+        for y in 0..map.height() as isize {
+            match map[Coordinate::new(y, x)] {
+                '#' => free = y + 1,
+                'O' => {
+                    map.swap(&Coordinate::new(y, x), &Coordinate::new(free, x));
+                    free += 1;
+                }
+                _ => {}
             }
         }
     }
 }
 
-fn new_position(y: usize, x: usize, map: &[Vec<char>]) -> Option<(usize, usize)> {
-    (0..y)
-        .rev()
-        .map(|y| (y, map[y][x]))
-        .take_while(|(_, tile)| *tile == '.')
-        .map(|(y, _)| (y, x))
-        .last()
-}
-
-fn spin_cycle(map: Vec<Vec<char>>) -> Vec<Vec<char>> {
-    (0..4).fold(map, |mut prev, _| {
-        tilt(&mut prev);
-        rotate(prev)
-    })
-}
-
-fn rotate(map: Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let len = map[0].len();
-    let mut iters: Vec<_> = map.into_iter().map(|n| n.into_iter()).collect();
-    (0..len)
-        .map(|_| {
-            iters
-                .iter_mut()
-                .map(|n| n.next().unwrap())
-                .collect::<Vec<char>>()
-        })
-        .map(|row| row.into_iter().rev().collect())
-        .collect()
+fn spin_cycle(map: &mut Grid<char>) {
+    for _ in 0..4 {
+        tilt(map);
+        map.rotate_cw();
+    }
 }
 
-fn total_load(map: &[Vec<char>]) -> u64 {
+fn total_load(map: &Grid<char>) -> u64 {
     map.iter()
-        .enumerate()
-        .flat_map(|(y, row)| row.iter().map(move |tile| (y, *tile)))
-        .filter(|(_, tile)| *tile == 'O')
-        .fold(0, |sum, (y, _)| sum + (map.len() - y) as u64)
+        .filter(|(_, tile)| **tile == 'O')
+        .map(|(c, _)| (map.height() as isize - c.y) as u64)
+        .sum()
 }
 
-fn calculate_hash(map: &Vec<Vec<char>>) -> u64 {
+fn calculate_hash(map: &Grid<char>) -> u64 {
     let mut hasher = DefaultHasher::new();
     map.hash(&mut hasher);
     hasher.finish()
 }
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,7 +86,7 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!(part1(input), 136);
+        assert_eq!(part1(input).unwrap(), 136);
     }
 
     #[test]
@@ -126,6 +101,6 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!(part2(input), 64);
+        assert_eq!(part2(input).unwrap(), 64);
     }
 }
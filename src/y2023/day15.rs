@@ -1,5 +1,4 @@
-use std::ops::Not;
-
+use crate::error::{Error, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -7,39 +6,35 @@ lazy_static! {
     static ref STEP: Regex = Regex::new(r"([a-z]+)(-|=)(\d*)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2);
 
 fn part1(input: &str) -> u32 {
     input.split(',').map(|s| hash_str(s) as u32).sum()
 }
 
-fn part2(input: &str) -> usize {
-    let steps = parse_steps(input);
+fn part2(input: &str) -> Result<usize> {
+    let steps = parse_steps(input)?;
     let mut boxes: [Vec<(String, u8)>; 256] = [(); 256].map(|_| Vec::new());
-    for (label, sign, focal_length) in steps {
+    for (label, operation) in steps {
         let lens_box = &mut boxes[hash_str(&label)];
         let slot_index = lens_box.iter().position(|(l, _)| *l == label);
-        match sign {
-            '-' => {
+        match operation {
+            Operation::Remove => {
                 if let Some(index) = slot_index {
                     lens_box.remove(index);
                 }
             }
-            '=' => match slot_index {
+            Operation::Insert(focal_length) => match slot_index {
                 Some(index) => {
-                    lens_box[index] = (label, focal_length.unwrap());
+                    lens_box[index] = (label, focal_length);
                 }
                 None => {
-                    lens_box.push((label, focal_length.unwrap()));
+                    lens_box.push((label, focal_length));
                 }
             },
-            _ => panic!("Unexpected sign"),
         }
     }
-    boxes
+    Ok(boxes
         .into_iter()
         .enumerate()
         .flat_map(|(box_number, lens_box)| {
@@ -50,7 +45,7 @@ fn part2(input: &str) -> usize {
                     (1 + box_number) * (1 + slot) * focal_length as usize
                 })
         })
-        .sum()
+        .sum())
 }
 
 fn hash_str(input: &str) -> usize {
@@ -61,19 +56,26 @@ fn hash(value: usize, c: char) -> usize {
     ((value + (c as usize)) * 17) % 256
 }
 
-fn parse_steps(input: &str) -> Vec<(String, char, Option<u8>)> {
+enum Operation {
+    Remove,
+    Insert(u8),
+}
+
+fn parse_steps(input: &str) -> Result<Vec<(String, Operation)>> {
     input
+        .trim()
         .split(',')
-        .filter_map(|raw_step| STEP.captures(raw_step))
-        .map(|cap| {
-            (
-                cap[1].to_string(),
-                cap[2].chars().next().unwrap(),
-                cap[3]
-                    .is_empty()
-                    .not()
-                    .then(|| cap[3].parse::<u8>().unwrap()),
-            )
+        .map(|raw_step| {
+            let invalid = || Error::parse(format!("invalid step '{}'", raw_step));
+            let cap = STEP.captures(raw_step).ok_or_else(invalid)?;
+            let operation = match (&cap[2], &cap[3]) {
+                ("-", "") => Operation::Remove,
+                ("=", focal_length) if !focal_length.is_empty() => {
+                    Operation::Insert(focal_length.parse()?)
+                }
+                _ => return Err(invalid()),
+            };
+            Ok((cap[1].to_string(), operation))
         })
         .collect()
 }
@@ -91,6 +93,7 @@ mod tests {
     #[test]
     fn test_part2() {
         let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!(part2(input), 145);
+        assert_eq!(part2(input).unwrap(), 145);
+        assert!(part2("rn=1,cm=").is_err());
     }
 }
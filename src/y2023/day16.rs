@@ -1,27 +1,28 @@
 use std::collections::{HashSet, VecDeque};
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+use crate::error::{Error, Result};
+use crate::util::grid::Grid;
+
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let contraption = parse(input);
-    num_energized(&contraption, ((0, 0), (0, 1)))
+fn part1(input: &str) -> Result<usize> {
+    let contraption = parse(input)?;
+    Ok(num_energized(&contraption, ((0, 0), (0, 1))))
 }
 
-fn part2(input: &str) -> usize {
-    let contraption = parse(input);
+fn part2(input: &str) -> Result<usize> {
+    let contraption = parse(input)?;
     let down = (0..contraption[0].len()).map(|x| ((0usize, x), (1isize, 0isize)));
     let right = (0..contraption.len()).map(|y| ((y, 0usize), (0isize, 1isize)));
     let left = (0..contraption.len()).map(|y| ((y, contraption[0].len() - 1), (0isize, -1isize)));
     let up = (0..contraption[0].len()).map(|x| ((contraption.len() - 1, x), (-1isize, 0isize)));
-    down.chain(right)
+    Ok(down
+        .chain(right)
         .chain(left)
         .chain(up)
         .map(|start| num_energized(&contraption, start))
         .max()
-        .unwrap()
+        .unwrap_or_default())
 }
 
 fn num_energized(contraption: &[Vec<char>], start: ((usize, usize), (isize, isize))) -> usize {
@@ -97,8 +98,15 @@ fn new_pos(
     Some((y, x))
 }
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn parse(input: &str) -> Result<Vec<Vec<char>>> {
+    let grid = Grid::parse_with(input, |c| match c {
+        '.' | '|' | '-' | '/' | '\\' => Ok(c),
+        _ => Err(()),
+    })?;
+    if grid.is_empty() {
+        return Err(Error::parse("empty contraption"));
+    }
+    Ok((0..grid.height()).map(|y| grid.row(y).to_vec()).collect())
 }
 
 #[cfg(test)]
@@ -117,7 +125,8 @@ mod tests {
 .-.-/..|..
 .|....-|.\\
 ..//.|....";
-        assert_eq!(part1(input), 46);
+        assert_eq!(part1(input).unwrap(), 46);
+        assert!(part1("").is_err());
     }
 
     #[test]
@@ -132,6 +141,6 @@ mod tests {
 .-.-/..|..
 .|....-|.\\
 ..//.|....";
-        assert_eq!(part2(input), 51);
+        assert_eq!(part2(input).unwrap(), 51);
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::{parse_lines, Error, Result};
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -7,53 +8,54 @@ lazy_static! {
         Regex::new(r"([RDLU]{1}) (\d+) \((#[a-z0-9]{6})\)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse_dig_plan);
 
-fn part1(input: &str) -> i64 {
-    let dig_plan = parse_dig_plan(input)
+fn part1(input: &str) -> Result<i64> {
+    let dig_plan = parse_dig_plan(input)?
         .into_iter()
         .map(|(direction, distance, _)| (direction, distance))
         .collect_vec();
     let lagoon = Lagoon::new(dig_plan);
-    lagoon.area() + (lagoon.perimeter / 2 + 1)
+    Ok(lagoon.area() + (lagoon.perimeter / 2 + 1))
 }
 
-fn part2(input: &str) -> i64 {
-    let dig_plan = parse_dig_plan(input)
+fn part2(input: &str) -> Result<i64> {
+    let dig_plan = parse_dig_plan(input)?
         .into_iter()
         .map(|(_, _, hex)| {
-            (
-                match &hex[6..7] {
-                    "0" => 'R',
-                    "1" => 'D',
-                    "2" => 'L',
-                    "3" => 'U',
-                    _ => panic!("Unexpected direction"),
-                },
-                i64::from_str_radix(&hex[1..6], 16).unwrap(),
-            )
+            let direction = match &hex[6..7] {
+                "0" => 'R',
+                "1" => 'D',
+                "2" => 'L',
+                "3" => 'U',
+                _ => return Err(Error::parse(format!("invalid color '{}'", hex))),
+            };
+            Ok((unit(direction)?, i64::from_str_radix(&hex[1..6], 16)?))
         })
-        .collect_vec();
+        .collect::<Result<Vec<_>>>()?;
     let lagoon = Lagoon::new(dig_plan);
-    lagoon.area() + (lagoon.perimeter / 2 + 1)
+    Ok(lagoon.area() + (lagoon.perimeter / 2 + 1))
 }
 
-fn parse_dig_plan(input: &str) -> Vec<(char, i64, String)> {
-    input
-        .lines()
-        .flat_map(|line| {
-            DIG_INSTRUCTION.captures(line).map(|cap| {
-                (
-                    cap[1].chars().next().unwrap(),
-                    cap[2].parse().unwrap(),
-                    cap[3].to_string(),
-                )
-            })
-        })
-        .collect()
+fn parse_dig_plan(input: &str) -> Result<Vec<(Point, i64, String)>> {
+    parse_lines(input, |line| -> Result<_> {
+        let cap = DIG_INSTRUCTION
+            .captures(line)
+            .ok_or_else(|| Error::parse(format!("invalid instruction '{}'", line)))?;
+        let direction = cap[1].chars().next().unwrap_or_default();
+        Ok((unit(direction)?, cap[2].parse()?, cap[3].to_string()))
+    })
+}
+
+/// One step in the direction `direction` names.
+fn unit(direction: char) -> Result<Point> {
+    match direction {
+        'L' => Ok(Point::new(-1, 0)),
+        'U' => Ok(Point::new(0, -1)),
+        'R' => Ok(Point::new(1, 0)),
+        'D' => Ok(Point::new(0, 1)),
+        _ => Err(Error::parse(format!("invalid direction '{}'", direction))),
+    }
 }
 
 struct Point {
@@ -79,17 +81,11 @@ struct Lagoon {
 }
 
 impl Lagoon {
-    pub fn new(dig_plan: Vec<(char, i64)>) -> Self {
+    pub fn new(dig_plan: Vec<(Point, i64)>) -> Self {
         let mut points = vec![Point::new(0, 0)];
         let mut perimeter = 0;
         for (direction, distance) in dig_plan {
-            let point = match direction {
-                'L' => Point::new(-distance, 0),
-                'U' => Point::new(0, -distance),
-                'R' => Point::new(distance, 0),
-                'D' => Point::new(0, distance),
-                _ => panic!("Unexpected direction"),
-            };
+            let point = Point::new(direction.x * distance, direction.y * distance);
             if let Some(prev) = points.last() {
                 points.push(prev.add(point));
                 perimeter += distance;
@@ -132,7 +128,7 @@ R 2 (#7807d2)
 U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)";
-        assert_eq!(part1(input), 62);
+        assert_eq!(part1(input).unwrap(), 62);
     }
 
     #[test]
@@ -151,6 +147,6 @@ R 2 (#7807d2)
 U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)";
-        assert_eq!(part2(input), 952408144115);
+        assert_eq!(part2(input).unwrap(), 952408144115);
     }
 }
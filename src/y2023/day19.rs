@@ -1,3 +1,4 @@
+use crate::error::{parse_lines, Error, Result};
 use std::{
     collections::{HashMap, VecDeque},
     ops::Range,
@@ -14,39 +15,34 @@ lazy_static! {
     static ref CONDITION: Regex = Regex::new(r"([xmas])([<>])(\d+):([a-zA-Z]+)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> u32 {
-    let (workflows, parts) = parse(input).unwrap();
-    parts
-        .into_iter()
-        .filter(|part| {
-            let mut next = "in";
-            loop {
-                let workflow = workflows.get(next).unwrap();
-                match workflow.execute(part) {
-                    "A" => return true,
-                    "R" => return false,
-                    id => {
-                        next = id;
-                    }
-                }
+fn part1(input: &str) -> Result<u32> {
+    let (workflows, parts) = parse(input)?;
+    let mut total = 0;
+    for part in parts {
+        let mut next = "in";
+        let accepted = loop {
+            match workflow(&workflows, next)?.execute(&part) {
+                "A" => break true,
+                "R" => break false,
+                id => next = id,
             }
-        })
-        .map(|part| part.sum())
-        .sum()
+        };
+        if accepted {
+            total += part.sum();
+        }
+    }
+    Ok(total)
 }
 
-fn part2(input: &str) -> usize {
-    let (workflows, _) = parse(input).unwrap();
+fn part2(input: &str) -> Result<usize> {
+    let (workflows, _) = parse(input)?;
     let range = PartRange::new();
     let mut to_investigate = vec![("in", range)].into_iter().collect::<VecDeque<_>>();
     let mut accepted = vec![];
     while let Some((id, part_range)) = to_investigate.pop_front() {
-        let workflow = workflows.get(id).unwrap();
+        let workflow = workflow(&workflows, id)?;
         let mut range = part_range;
         for condition in workflow.conditions.iter() {
             let (success, fail) = condition.apply_range(&range);
@@ -63,29 +59,46 @@ fn part2(input: &str) -> usize {
             to_investigate.push_back((&workflow.fallback, range))
         }
     }
-    accepted
+    Ok(accepted
         .into_iter()
         .map(|part_range| part_range.permutations())
-        .sum()
+        .sum())
+}
+
+fn workflow<'a>(workflows: &'a HashMap<String, Workflow>, id: &str) -> Result<&'a Workflow> {
+    workflows
+        .get(id)
+        .ok_or_else(|| Error::parse(format!("unknown workflow '{}'", id)))
 }
 
-fn parse(input: &str) -> Option<(HashMap<String, Workflow>, Vec<Part>)> {
-    let (raw_workflows, raw_parts) = input.split_once("\n\n")?;
-    Some((
-        raw_workflows
-            .lines()
-            .flat_map(Workflow::from_str)
-            .map(|workflow| (workflow.id.clone(), workflow))
-            .collect(),
-        raw_parts.lines().flat_map(Part::from_str).collect_vec(),
-    ))
+fn parse(input: &str) -> Result<(HashMap<String, Workflow>, Vec<Part>)> {
+    let (raw_workflows, raw_parts) = input
+        .split_once("\n\n")
+        .ok_or_else(|| Error::parse("expected workflows and parts separated by a blank line"))?;
+    let workflows = parse_lines(raw_workflows, Workflow::from_str)?
+        .into_iter()
+        .map(|workflow| (workflow.id.clone(), workflow))
+        .collect();
+    // The parts start after the workflows and the blank line
+    let offset = raw_workflows.lines().count() + 1;
+    let parts = raw_parts
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Part::from_str(line).map_err(|e| e.at_line(offset + i + 1)))
+        .collect::<Result<_>>()?;
+    Ok((workflows, parts))
+}
+
+#[derive(Debug)]
+enum Operator {
+    Less,
+    Greater,
 }
 
-struct ParseConditionError;
 #[derive(Debug)]
 struct Condition {
     category: usize,
-    operator: char,
+    operator: Operator,
     threshold: u32,
     target: String,
 }
@@ -96,15 +109,14 @@ impl Condition {
         let mut fail = PartRange::from(part);
         let current = &part.ranges[self.category];
         let (s, f) = match self.operator {
-            '>' => (
+            Operator::Greater => (
                 current.start.max(self.threshold + 1)..(current.end.min(4001)),
                 current.start.max(1)..current.end.min(self.threshold + 1),
             ),
-            '<' => (
+            Operator::Less => (
                 current.start.max(1)..current.end.min(self.threshold),
                 current.start.max(self.threshold)..(current.end.min(4001)),
             ),
-            _ => panic!("Unexpected operator"),
         };
         success.ranges[self.category] = s;
         fail.ranges[self.category] = f;
@@ -113,45 +125,30 @@ impl Condition {
     pub fn apply(&self, part: &Part) -> Option<&str> {
         let value = part.values[self.category];
         match self.operator {
-            '<' => value < self.threshold,
-            '>' => value > self.threshold,
-            _ => panic!("Unexpected operator"),
+            Operator::Less => value < self.threshold,
+            Operator::Greater => value > self.threshold,
         }
         .then_some(&self.target)
     }
 }
 impl FromStr for Condition {
-    type Err = ParseConditionError;
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let caps = CONDITION.captures(input).ok_or(ParseConditionError)?;
+    type Err = Error;
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || Error::parse(format!("invalid condition '{}'", input));
+        let caps = CONDITION.captures(input).ok_or_else(invalid)?;
         Ok(Condition {
-            category: match caps[1]
-                .to_string()
-                .chars()
-                .next()
-                .ok_or(ParseConditionError)?
-            {
-                'x' => 0,
-                'm' => 1,
-                'a' => 2,
-                's' => 3,
-                _ => panic!("Unexpected category"),
+            category: "xmas".find(&caps[1]).ok_or_else(invalid)?,
+            operator: match &caps[2] {
+                "<" => Operator::Less,
+                ">" => Operator::Greater,
+                _ => return Err(invalid()),
             },
-            operator: caps[2]
-                .to_string()
-                .chars()
-                .next()
-                .ok_or(ParseConditionError)?,
-            threshold: caps[3]
-                .to_string()
-                .parse()
-                .map_err(|_| ParseConditionError)?,
+            threshold: caps[3].parse()?,
             target: caps[4].to_string(),
         })
     }
 }
 
-struct ParseWorkflowError;
 #[derive(Debug)]
 struct Workflow {
     id: String,
@@ -170,17 +167,22 @@ impl Workflow {
     }
 }
 impl FromStr for Workflow {
-    type Err = ParseWorkflowError;
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let workflow = WORKFLOW.captures(input).ok_or(ParseWorkflowError)?;
+    type Err = Error;
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        let workflow = WORKFLOW
+            .captures(input)
+            .ok_or_else(|| Error::parse(format!("invalid workflow '{}'", input)))?;
         let raw_conditions = workflow[2].split(',').collect_vec();
+        let (fallback, raw_conditions) = raw_conditions
+            .split_last()
+            .ok_or_else(|| Error::parse(format!("invalid workflow '{}'", input)))?;
         Ok(Workflow {
             id: workflow[1].to_string(),
-            fallback: raw_conditions.last().ok_or(ParseWorkflowError)?.to_string(),
+            fallback: fallback.to_string(),
             conditions: raw_conditions
-                .into_iter()
-                .flat_map(Condition::from_str)
-                .collect(),
+                .iter()
+                .map(|raw| Condition::from_str(raw))
+                .collect::<Result<_>>()?,
         })
     }
 }
@@ -206,7 +208,6 @@ impl PartRange {
     }
 }
 
-struct ParsePartError;
 #[derive(Debug)]
 struct Part {
     values: [u32; 4],
@@ -219,23 +220,19 @@ impl Part {
 }
 
 impl FromStr for Part {
-    type Err = ParsePartError;
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        PART.captures(input)
-            .and_then(|cap| {
-                match (
-                    cap[1].parse(),
-                    cap[2].parse(),
-                    cap[3].parse(),
-                    cap[4].parse(),
-                ) {
-                    (Ok(x), Ok(m), Ok(a), Ok(s)) => Some(Part {
-                        values: [x, m, a, s],
-                    }),
-                    _ => None,
-                }
-            })
-            .ok_or(ParsePartError)
+    type Err = Error;
+    fn from_str(input: &str) -> std::result::Result<Self, Self::Err> {
+        let cap = PART
+            .captures(input)
+            .ok_or_else(|| Error::parse(format!("invalid part '{}'", input)))?;
+        Ok(Part {
+            values: [
+                cap[1].parse()?,
+                cap[2].parse()?,
+                cap[3].parse()?,
+                cap[4].parse()?,
+            ],
+        })
     }
 }
 
@@ -262,7 +259,7 @@ hdj{m>838:A,pv}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
-        assert_eq!(part1(input), 19114);
+        assert_eq!(part1(input).unwrap(), 19114);
     }
 
     #[test]
@@ -284,6 +281,20 @@ hdj{m>838:A,pv}
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
-        assert_eq!(part2(input), 167409079868000);
+        assert_eq!(part2(input).unwrap(), 167409079868000);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let error = part1("in{x<5:A,R}\n\n{x=1,m=2,a=3,s=4}\n{x=1}").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse line 4: invalid part '{x=1}'"
+        );
+        let error = part1("in{x<5:foo,R}\n\n{x=1,m=2,a=3,s=4}").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse input: unknown workflow 'foo'"
+        );
     }
 }
@@ -1,5 +1,6 @@
 use std::{collections::HashMap, str::FromStr};
 
+use crate::error::{parse_lines, Error, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -7,26 +8,21 @@ lazy_static! {
     static ref ROUND_PART: Regex = Regex::new(r"(\d+) (red|blue|green)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .flat_map(Game::from_str)
+fn part1(input: &str) -> Result<u32> {
+    Ok(parse(input)?
+        .into_iter()
         .filter(|game| {
             game.max_of("red") <= 12 && game.max_of("green") <= 13 && game.max_of("blue") <= 14
         })
         .map(|game| game.id)
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> u32 {
-    input
-        .lines()
-        .flat_map(Game::from_str)
+fn part2(input: &str) -> Result<u32> {
+    Ok(parse(input)?
+        .into_iter()
         .map(|game| {
             vec![
                 game.max_of("red"),
@@ -36,11 +32,12 @@ fn part2(input: &str) -> u32 {
             .into_iter()
             .product::<u32>()
         })
-        .sum()
+        .sum())
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseGameError;
+fn parse(input: &str) -> Result<Vec<Game>> {
+    parse_lines(input, Game::from_str)
+}
 
 struct Game {
     id: u32,
@@ -48,21 +45,24 @@ struct Game {
 }
 
 impl FromStr for Game {
-    type Err = ParseGameError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(':').collect::<Vec<&str>>();
-        let raw_title = parts.first().ok_or(ParseGameError)?.replace("Game ", "");
-        let raw_rounds = parts.get(1).ok_or(ParseGameError)?.split(';');
+    type Err = Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (raw_title, raw_rounds) = s
+            .split_once(':')
+            .ok_or_else(|| Error::parse("missing ':'"))?;
+        let id = raw_title
+            .strip_prefix("Game ")
+            .ok_or_else(|| Error::parse(format!("invalid game '{}'", raw_title)))?
+            .parse::<u32>()?;
         let rounds = raw_rounds
-            .into_iter()
+            .split(';')
             .map(|raw_round| {
                 ROUND_PART
                     .captures_iter(raw_round)
-                    .map(|caps| (caps[2].to_owned(), caps[1].parse::<u32>().unwrap()))
+                    .map(|caps| Ok((caps[2].to_owned(), caps[1].parse::<u32>()?)))
                     .collect()
             })
-            .collect::<Vec<HashMap<String, u32>>>();
-        let id = raw_title.parse::<u32>().map_err(|_| ParseGameError)?;
+            .collect::<Result<Vec<HashMap<String, u32>>>>()?;
         Ok(Game { id, rounds })
     }
 }
@@ -87,7 +87,7 @@ Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!(8, part1(input));
+        assert_eq!(8, part1(input).unwrap());
     }
 
     #[test]
@@ -97,6 +97,7 @@ Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!(2286, part2(input));
+        assert_eq!(2286, part2(input).unwrap());
+        assert!(part2("Game x: 1 red").is_err());
     }
 }
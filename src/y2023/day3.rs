@@ -1,5 +1,6 @@
 use std::ops::Range;
 
+use crate::error::Result;
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -7,45 +8,39 @@ lazy_static! {
     static ref NUMBERS: Regex = Regex::new(r"(\d+)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> u32 {
+fn part1(input: &str) -> Result<u32> {
     let parsed = parse(input);
-    let numbers = numbers(&parsed);
-    symbol_coordinates(&parsed)
+    let numbers = numbers(&parsed)?;
+    Ok(symbol_coordinates(&parsed)
         .iter()
         .map(|(symbol, coordinate)| (symbol, adjacent_numbers(&numbers, coordinate)))
         .map(|(_, numbers)| numbers.into_iter().sum::<u32>())
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> u32 {
+fn part2(input: &str) -> Result<u32> {
     let parsed = parse(input);
-    let numbers = numbers(&parsed);
-    symbol_coordinates(&parsed)
+    let numbers = numbers(&parsed)?;
+    Ok(symbol_coordinates(&parsed)
         .iter()
         .map(|(symbol, coordinate)| (symbol, adjacent_numbers(&numbers, coordinate)))
         .filter(|(symbol, numbers)| **symbol == '*' && numbers.len() == 2)
         .map(|(_, numbers)| numbers.into_iter().product::<u32>())
-        .sum()
+        .sum())
 }
 
-fn adjacent_numbers(
-    number_ranges: &[Vec<(u32, Range<usize>)>],
-    symbol_pos: &Coordinate,
-) -> Vec<u32> {
+fn adjacent_numbers(number_ranges: &[NumberRanges], symbol_pos: &Coordinate) -> Vec<u32> {
     number_ranges
         .iter()
         .take(symbol_pos.0 + 2)
-        .skip(symbol_pos.0 - 1)
+        .skip(symbol_pos.0.saturating_sub(1))
         .flat_map(|ranges| {
             ranges
                 .iter()
                 .filter(|(_, n_range)| {
-                    (symbol_pos.1 - 1..symbol_pos.1 + 2).any(|x| n_range.contains(&x))
+                    (symbol_pos.1.saturating_sub(1)..symbol_pos.1 + 2).any(|x| n_range.contains(&x))
                 })
                 .map(|(n, _)| *n)
         })
@@ -70,20 +65,27 @@ fn symbol_coordinates(schematic: &[String]) -> Vec<(char, Coordinate)> {
         .collect()
 }
 
-fn numbers(schematic: &[String]) -> Vec<Vec<(u32, Range<usize>)>> {
-    schematic.iter().map(|row| number_ranges(row)).collect()
+fn numbers(schematic: &[String]) -> Result<Vec<NumberRanges>> {
+    schematic
+        .iter()
+        .enumerate()
+        .map(|(y, row)| number_ranges(row).map_err(|e| e.at_line(y + 1)))
+        .collect()
 }
 
-fn number_ranges(row: &str) -> Vec<(u32, Range<usize>)> {
+fn number_ranges(row: &str) -> Result<NumberRanges> {
     NUMBERS
         .captures_iter(row)
         .flat_map(|captures| captures.get(1))
-        .map(|m| (m.as_str().parse::<u32>().unwrap(), m.range()))
+        .map(|m| Ok((m.as_str().parse::<u32>()?, m.range())))
         .collect()
 }
 
 type Coordinate = (usize, usize);
 
+/// The numbers in a row, with the columns they span.
+type NumberRanges = Vec<(u32, Range<usize>)>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,7 +102,7 @@ mod tests {
 ......755.
 ...$.*....
 .664.598..";
-        assert_eq!(part1(input), 4361);
+        assert_eq!(part1(input).unwrap(), 4361);
     }
 
     #[test]
@@ -115,6 +117,6 @@ mod tests {
 ......755.
 ...$.*....
 .664.598..";
-        assert_eq!(part2(input), 467835);
+        assert_eq!(part2(input).unwrap(), 467835);
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::error::{parse_lines, Error, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -7,58 +8,56 @@ lazy_static! {
     static ref CARD: Regex = Regex::new(r"Card +(\d+): ([\d ]+) \| ([\d ]+)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> u32 {
-    parse(input)
+fn part1(input: &str) -> Result<u32> {
+    Ok(parse(input)?
         .into_iter()
         .map(|game| num_matching(&game))
         .map(|num_matching| (0..num_matching).fold(0, |score, _| (score * 2).max(1)))
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> u32 {
-    let games = parse(input).into_iter().collect::<Vec<_>>();
+fn part2(input: &str) -> Result<u32> {
+    let games = parse(input)?;
     let mut card_counts = games
         .iter()
         .map(|game| (game.0, 1))
         .collect::<HashMap<_, _>>();
     for game in games.iter() {
-        let num_cards = *card_counts.get(&game.0).unwrap();
+        let num_cards = card_counts[&game.0];
         for n in 0..num_matching(game) {
             let id = game.0 + (n as u32) + 1;
             *card_counts.entry(id).or_default() += num_cards;
         }
     }
-    card_counts.values().sum()
+    Ok(card_counts.values().sum())
 }
 
-fn num_matching(card: &(u32, HashSet<u32>, Vec<u32>)) -> usize {
+fn num_matching(card: &Card) -> usize {
     card.2.iter().filter(|n| card.1.contains(n)).count()
 }
 
-fn parse(input: &str) -> Vec<(u32, HashSet<u32>, Vec<u32>)> {
-    input
-        .lines()
-        .filter_map(|line| {
-            CARD.captures(line).map(|cap| {
-                (
-                    cap[1].parse::<u32>().ok().unwrap(),
-                    cap[2]
-                        .split(' ')
-                        .filter_map(|n| n.parse::<u32>().ok())
-                        .collect(),
-                    cap[3]
-                        .split(' ')
-                        .filter_map(|n| n.parse::<u32>().ok())
-                        .collect(),
-                )
-            })
-        })
-        .collect()
+/// A card's id, winning numbers and the numbers on it.
+type Card = (u32, HashSet<u32>, Vec<u32>);
+
+fn parse(input: &str) -> Result<Vec<Card>> {
+    parse_lines(input, |line| -> Result<_> {
+        let cap = CARD
+            .captures(line)
+            .ok_or_else(|| Error::parse(format!("invalid card '{}'", line)))?;
+        Ok((
+            cap[1].parse::<u32>()?,
+            cap[2]
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<std::result::Result<_, _>>()?,
+            cap[3]
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<std::result::Result<_, _>>()?,
+        ))
+    })
 }
 
 #[cfg(test)]
@@ -73,7 +72,7 @@ Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!(part1(input), 13);
+        assert_eq!(part1(input).unwrap(), 13);
     }
 
     #[test]
@@ -84,6 +83,6 @@ Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!(part2(input), 30);
+        assert_eq!(part2(input).unwrap(), 30);
     }
 }
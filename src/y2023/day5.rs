@@ -1,5 +1,6 @@
 use std::ops::Range;
 
+use crate::error::{Error, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -7,29 +8,31 @@ lazy_static! {
     static ref NUMBERS: Regex = Regex::new(r"(\d+)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {:?}", part1(input));
-    println!("Part 2: {:?}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> Option<u64> {
-    let (seeds, resource_maps) = parse(input);
+fn part1(input: &str) -> Result<u64> {
+    let (seeds, resource_maps) = parse(input)?;
     seeds
         .into_iter()
         .map(|seed| seed..seed + 1)
         .flat_map(|seed| seed_to_location(seed, &resource_maps))
         .map(|range| range.start)
         .min()
+        .ok_or_else(|| Error::NoAnswer("no seeds".to_string()))
 }
 
-fn part2(input: &str) -> Option<u64> {
-    let (seeds, resource_maps) = parse(input);
+fn part2(input: &str) -> Result<u64> {
+    let (seeds, resource_maps) = parse(input)?;
+    if seeds.len() % 2 != 0 {
+        return Err(Error::parse("seeds are not in start and length pairs"));
+    }
     seeds
-        .chunks(2)
+        .chunks_exact(2)
         .map(|chunk| chunk[0]..chunk[0] + chunk[1])
         .flat_map(|range| seed_to_location(range, &resource_maps))
         .map(|range| range.start)
         .min()
+        .ok_or_else(|| Error::NoAnswer("no seeds".to_string()))
 }
 
 fn seed_to_location(input: Range<u64>, resource_maps: &[ResourceMap]) -> Vec<Range<u64>> {
@@ -76,37 +79,33 @@ fn translate((range, diff): (Range<u64>, isize)) -> Range<u64> {
     ((range.start as isize) + diff) as u64..((range.end as isize) + diff) as u64
 }
 
-fn parse(input: &str) -> (Vec<u64>, Vec<ResourceMap>) {
-    let mut parts = input.split("\n\n");
-    let seeds = NUMBERS
-        .captures_iter(parts.next().unwrap())
-        .map(|cap| cap[1].parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
-    (
-        seeds,
-        parts
-            .map(|part| {
-                let mut ranges = part
-                    .lines()
-                    .skip(1)
-                    .map(|line| {
-                        NUMBERS
-                            .captures_iter(line)
-                            .map(|cap| cap[1].parse::<u64>().unwrap())
-                            .collect::<Vec<_>>()
-                    })
-                    .map(|numbers| {
-                        (
-                            numbers[0]..numbers[0] + numbers[2],
-                            numbers[1]..numbers[1] + numbers[2],
-                        )
-                    })
-                    .collect::<Vec<_>>();
-                ranges.sort_by(|(_, a_src), (_, b_src)| a_src.start.cmp(&b_src.start));
-                ranges
-            })
-            .collect(),
-    )
+fn parse(input: &str) -> Result<(Vec<u64>, Vec<ResourceMap>)> {
+    let (seeds, maps) = input.split_once("\n\n").unwrap_or((input, ""));
+    let seeds = numbers(seeds)?;
+    let resource_maps = maps
+        .split("\n\n")
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut ranges = part
+                .lines()
+                .skip(1)
+                .map(|line| match numbers(line)?.as_slice() {
+                    &[dst, src, len] => Ok((dst..dst + len, src..src + len)),
+                    _ => Err(Error::parse(format!("invalid range '{}'", line))),
+                })
+                .collect::<Result<ResourceMap>>()?;
+            ranges.sort_by_key(|(_, src)| src.start);
+            Ok(ranges)
+        })
+        .collect::<Result<_>>()?;
+    Ok((seeds, resource_maps))
+}
+
+fn numbers(line: &str) -> Result<Vec<u64>> {
+    NUMBERS
+        .captures_iter(line)
+        .map(|cap| Ok(cap[1].parse::<u64>()?))
+        .collect()
 }
 
 type ResourceMap = Vec<(Range<u64>, Range<u64>)>;
@@ -150,7 +149,7 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4";
-        assert_eq!(part1(input), Some(35));
+        assert_eq!(part1(input).unwrap(), 35);
     }
 
     #[test]
@@ -188,6 +187,6 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4";
-        assert_eq!(part2(input), Some(46));
+        assert_eq!(part2(input).unwrap(), 46);
     }
 }
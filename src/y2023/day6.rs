@@ -1,3 +1,4 @@
+use crate::error::{parse_lines, Error, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -5,30 +6,26 @@ lazy_static! {
     static ref NUMBERS: Regex = Regex::new(r"(\d+)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> u64 {
-    let parsed = parse(input);
-    let races: Vec<_> = (0..parsed[0].len())
-        .map(|i| (parsed[0][i], parsed[1][i]))
-        .collect();
-    races.iter().map(num_wins).product()
+fn part1(input: &str) -> Result<u64> {
+    let (times, records) = parse(input)?;
+    Ok(times
+        .into_iter()
+        .zip(records)
+        .map(|race| num_wins(&race))
+        .product())
 }
 
-fn part2(input: &str) -> u64 {
-    let race: Vec<_> = parse(input)
-        .into_iter()
-        .map(|l| {
-            l.into_iter()
-                .fold(String::new(), |s, x| format!("{}{}", s, x))
-                .parse::<u64>()
-                .unwrap()
-        })
-        .collect();
-    num_wins(&(race[0], race[1]))
+fn part2(input: &str) -> Result<u64> {
+    let (times, records) = parse(input)?;
+    let join = |numbers: Vec<u64>| {
+        numbers
+            .into_iter()
+            .fold(String::new(), |s, x| format!("{}{}", s, x))
+            .parse::<u64>()
+    };
+    Ok(num_wins(&(join(times)?, join(records)?)))
 }
 
 fn num_wins(race: &(u64, u64)) -> u64 {
@@ -42,16 +39,20 @@ fn num_wins(race: &(u64, u64)) -> u64 {
     })
 }
 
-fn parse(input: &str) -> Vec<Vec<u64>> {
-    input
-        .lines()
-        .map(|line| {
-            NUMBERS
-                .captures_iter(line)
-                .flat_map(|cap| cap.get(1).map(|n| n.as_str().parse::<u64>().unwrap()))
-                .collect::<Vec<_>>()
-        })
-        .collect()
+/// The race times and the record distances.
+fn parse(input: &str) -> Result<(Vec<u64>, Vec<u64>)> {
+    let lines = parse_lines(input, |line| {
+        NUMBERS
+            .captures_iter(line)
+            .map(|cap| cap[1].parse::<u64>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+    })?;
+    match <[Vec<u64>; 2]>::try_from(lines) {
+        Ok([times, records]) if times.len() == records.len() => Ok((times, records)),
+        _ => Err(Error::parse(
+            "expected a line of times and one of distances",
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -62,13 +63,13 @@ mod tests {
     fn part1_test() {
         let input = "Time:      7  15   30
 Distance:  9  40  200";
-        assert_eq!(part1(input), 288);
+        assert_eq!(part1(input).unwrap(), 288);
     }
 
     #[test]
     fn part2_test() {
         let input = "Time:      7  15   30
 Distance:  9  40  200";
-        assert_eq!(part2(input), 71503);
+        assert_eq!(part2(input).unwrap(), 71503);
     }
 }
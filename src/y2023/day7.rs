@@ -3,47 +3,40 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
+use crate::error::{parse_lines, Error, Result};
 use itertools::Itertools;
 
 const CARDS: &str = "23456789TJQKA";
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2);
 
-fn part1(input: &str) -> u64 {
-    let mut hands = parse(input, false);
+fn part1(input: &str) -> Result<u64> {
+    let mut hands = parse(input, false)?;
     hands.sort();
-    hands
+    Ok(hands
         .into_iter()
         .enumerate()
         .map(|(rank, hand)| (rank as u64 + 1) * hand.bid)
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> u64 {
-    let mut hands = parse(input, true);
+fn part2(input: &str) -> Result<u64> {
+    let mut hands = parse(input, true)?;
     hands.sort();
-    hands
+    Ok(hands
         .into_iter()
         .enumerate()
         .map(|(rank, hand)| (rank as u64 + 1) * hand.bid)
-        .sum()
+        .sum())
 }
 
-fn parse(input: &str, with_joker: bool) -> Vec<Hand> {
-    input
-        .lines()
-        .map(|line| {
-            let (hand, bid) = line.split_once(' ').expect("No separator found");
-            Hand::new(
-                hand,
-                bid.parse::<u64>().expect("Not able to parse bid as number"),
-                with_joker,
-            )
-        })
-        .collect()
+fn parse(input: &str, with_joker: bool) -> Result<Vec<Hand>> {
+    parse_lines(input, |line| {
+        let (hand, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| Error::parse("missing bid"))?;
+        Hand::new(hand, bid.parse::<u64>()?, with_joker)
+    })
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -64,13 +57,16 @@ struct Hand {
 }
 
 impl Hand {
-    fn new(hand: &str, bid: u64, with_joker: bool) -> Self {
+    fn new(hand: &str, bid: u64, with_joker: bool) -> Result<Self> {
+        if hand.chars().count() != 5 || !hand.chars().all(|card| CARDS.contains(card)) {
+            return Err(Error::parse(format!("invalid hand '{}'", hand)));
+        }
         let cards = hand.chars();
-        Hand {
+        Ok(Hand {
             hand_type: hand_type(hand, with_joker),
             card_score: cards.map(|card| card_score(card, with_joker)).collect_vec(),
             bid,
-        }
+        })
     }
 }
 
@@ -120,11 +116,15 @@ fn hand_type(hand: &str, with_joker: bool) -> HandType {
     }
 }
 
+/// The strength of a card from [`CARDS`], which [`Hand::new`] checks all cards are.
 fn card_score(card: char, with_joker: bool) -> u32 {
     if card == 'J' && with_joker {
         return 0;
     }
-    CARDS.chars().position(|c| c == card).unwrap() as u32 + 1
+    CARDS
+        .chars()
+        .position(|c| c == card)
+        .map_or(0, |i| i as u32 + 1)
 }
 
 fn joker_replacement(hand: &str) -> char {
@@ -132,17 +132,10 @@ fn joker_replacement(hand: &str) -> char {
     for c in hand.chars().filter(|c| *c != 'J') {
         *counts.entry(c).or_insert(0) += 1;
     }
-    if counts.is_empty() {
-        return 'A';
-    }
-    let max_count = *counts.values().max().unwrap();
     counts
         .into_iter()
-        .filter(|(_, count)| *count == max_count)
-        .map(|(card, _)| (card, card_score(card, true)))
-        .max_by(|(_, a_score), (_, b_score)| a_score.cmp(b_score))
-        .map(|(card, _)| card)
-        .unwrap()
+        .max_by_key(|&(card, count)| (count, card_score(card, true)))
+        .map_or('A', |(card, _)| card)
 }
 
 #[cfg(test)]
@@ -156,7 +149,8 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        assert_eq!(part1(input), 6440);
+        assert_eq!(part1(input).unwrap(), 6440);
+        assert!(part1("32T3X 765").is_err());
     }
 
     #[test]
@@ -166,6 +160,6 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        assert_eq!(part2(input), 5905);
+        assert_eq!(part2(input).unwrap(), 5905);
     }
 }
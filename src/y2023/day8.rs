@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::error::{Error, Result};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -8,52 +9,54 @@ lazy_static! {
         Regex::new(r"([A-Z0-9]{3}) = \(([A-Z0-9]{3}), ([A-Z0-9]{3})\)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
+crate::solution!(part1, part2, parse);
+
+type Nodes = HashMap<String, (String, String)>;
+
+enum Step {
+    Left,
+    Right,
 }
 
-fn part1(input: &str) -> u64 {
-    let (steps, nodes) = parse(input);
-    let mut current = nodes.get("AAA").unwrap();
+fn part1(input: &str) -> Result<u64> {
+    let (steps, nodes) = parse(input)?;
+    let mut current = node(&nodes, "AAA")?;
     let mut num_steps = 0;
     for step in steps.iter().cycle() {
-        let next = match *step {
-            'L' => &current.0,
-            'R' => &current.1,
-            _ => {
-                panic!("Unexpected step");
-            }
+        let next = match step {
+            Step::Left => &current.0,
+            Step::Right => &current.1,
         };
-        current = nodes.get(next).unwrap();
+        current = node(&nodes, next)?;
         num_steps += 1;
         if next == "ZZZ" {
             break;
         }
     }
-    num_steps
+    Ok(num_steps)
 }
 
-fn part2(input: &str) -> usize {
-    let (steps, nodes) = parse(input);
+fn part2(input: &str) -> Result<usize> {
+    let (steps, nodes) = parse(input)?;
     nodes
         .keys()
         .filter(|id| id.ends_with('A'))
         .map(|start| find_step(start, &steps, &nodes))
-        .fold(1usize, |res, n| lcm(n, res))
+        .try_fold(1usize, |res, n| Ok(lcm(n?, res)))
 }
 
-fn find_step(start: &str, steps: &[char], nodes: &HashMap<String, (String, String)>) -> usize {
-    let mut current = nodes.get(start).unwrap();
+fn find_step(start: &str, steps: &[Step], nodes: &Nodes) -> Result<usize> {
+    let mut current = node(nodes, start)?;
     let mut end_indexes = vec![];
-    for (i, step) in steps.iter().cycle().enumerate() {
+    // Each (node, step) state repeats within this many steps, so an end that comes back does too
+    let limit = 2 * steps.len() * nodes.len();
+    for (i, step) in steps.iter().cycle().take(limit).enumerate() {
         let (left, right) = current;
-        let next = match *step {
-            'L' => left,
-            'R' => right,
-            _ => panic!("Unexpected step!"),
+        let next = match step {
+            Step::Left => left,
+            Step::Right => right,
         };
-        current = nodes.get(next).unwrap();
+        current = node(nodes, next)?;
         if next.ends_with('Z') {
             end_indexes.push(i + 1);
         }
@@ -61,24 +64,49 @@ fn find_step(start: &str, steps: &[char], nodes: &HashMap<String, (String, Strin
             break;
         }
     }
-    end_indexes[1] - end_indexes[0]
+    match end_indexes[..] {
+        [first, second] => Ok(second - first),
+        _ => Err(Error::NoAnswer(format!(
+            "no repeating end node reached from '{}'",
+            start
+        ))),
+    }
 }
 
-fn parse(input: &str) -> (Vec<char>, HashMap<String, (String, String)>) {
-    let mut parts = input.split("\n\n");
-    let steps = parts.next().unwrap().chars().collect::<Vec<_>>();
-    (
-        steps,
-        parts
-            .next()
-            .unwrap()
-            .lines()
-            .map(|line| {
-                let caps = NODE.captures(line).unwrap();
-                (caps[1].to_owned(), (caps[2].to_owned(), caps[3].to_owned()))
-            })
-            .collect::<HashMap<_, _>>(),
-    )
+fn node<'a>(nodes: &'a Nodes, id: &str) -> Result<&'a (String, String)> {
+    nodes
+        .get(id)
+        .ok_or_else(|| Error::parse(format!("unknown node '{}'", id)))
+}
+
+fn parse(input: &str) -> Result<(Vec<Step>, Nodes)> {
+    let mut lines = input.lines();
+    let steps = lines
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .map(|c| match c {
+            'L' => Ok(Step::Left),
+            'R' => Ok(Step::Right),
+            _ => Err(Error::parse(format!("invalid step '{}'", c)).at_line(1)),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if steps.is_empty() {
+        return Err(Error::parse("no steps").at_line(1));
+    }
+    if lines.next() != Some("") {
+        return Err(Error::parse("expected a blank line after the steps").at_line(2));
+    }
+    let nodes = lines
+        .enumerate()
+        .map(|(i, line)| {
+            let caps = NODE
+                .captures(line)
+                .ok_or_else(|| Error::parse(format!("invalid node '{}'", line)).at_line(i + 3))?;
+            Ok((caps[1].to_owned(), (caps[2].to_owned(), caps[3].to_owned())))
+        })
+        .collect::<Result<_>>()?;
+    Ok((steps, nodes))
 }
 
 fn lcm(a: usize, b: usize) -> usize {
@@ -112,8 +140,8 @@ ZZZ = (ZZZ, ZZZ)";
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(part1(input), 2);
-        assert_eq!(part1(input2), 6);
+        assert_eq!(part1(input).unwrap(), 2);
+        assert_eq!(part1(input2).unwrap(), 6);
     }
 
     #[test]
@@ -128,6 +156,22 @@ ZZZ = (ZZZ, ZZZ)";
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
-        assert_eq!(part2(input), 6);
+        assert_eq!(part2(input).unwrap(), 6);
+    }
+
+    #[test]
+    fn invalid_input_test() {
+        let error = part1("LR\n\nAAA = (ZZZ, ZZZ)\nZZZ").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse line 4: invalid node 'ZZZ'"
+        );
+        let error = part1("LR\n\nAAA = (BBB, BBB)").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse input: unknown node 'BBB'"
+        );
+        let error = part2("L\n\n11A = (11Z, 11Z)\n11Z = (XXX, XXX)\nXXX = (XXX, XXX)");
+        assert!(matches!(error, Err(Error::NoAnswer(_))));
     }
 }
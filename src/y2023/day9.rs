@@ -1,16 +1,15 @@
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+use crate::error::{parse_lines, Result};
+
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> i64 {
-    let histories = parse(input);
-    histories.into_iter().map(|history| next(&history)).sum()
+fn part1(input: &str) -> Result<i64> {
+    let histories = parse(input)?;
+    Ok(histories.into_iter().map(|history| next(&history)).sum())
 }
 
-fn part2(input: &str) -> i64 {
-    let histories = parse(input);
-    histories.into_iter().map(|history| prev(&history)).sum()
+fn part2(input: &str) -> Result<i64> {
+    let histories = parse(input)?;
+    Ok(histories.into_iter().map(|history| prev(&history)).sum())
 }
 
 fn next(history: &[i64]) -> i64 {
@@ -21,7 +20,8 @@ fn next(history: &[i64]) -> i64 {
         .windows(2)
         .map(|pair| pair[1] - pair[0])
         .collect::<Vec<_>>();
-    history.last().unwrap() + next(&diffs)
+    // Not all zero, so not empty
+    history[history.len() - 1] + next(&diffs)
 }
 
 fn prev(history: &[i64]) -> i64 {
@@ -32,18 +32,15 @@ fn prev(history: &[i64]) -> i64 {
         .windows(2)
         .map(|pair| pair[1] - pair[0])
         .collect::<Vec<_>>();
-    history.first().unwrap() - prev(&diffs)
+    history[0] - prev(&diffs)
 }
 
-fn parse(input: &str) -> Vec<Vec<i64>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split(' ')
-                .map(|raw| raw.parse::<_>().unwrap())
-                .collect()
-        })
-        .collect()
+fn parse(input: &str) -> Result<Vec<Vec<i64>>> {
+    parse_lines(input, |line| {
+        line.split_whitespace()
+            .map(str::parse)
+            .collect::<std::result::Result<_, _>>()
+    })
 }
 
 #[cfg(test)]
@@ -55,7 +52,7 @@ mod tests {
         let input = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
-        assert_eq!(part1(input), 114);
+        assert_eq!(part1(input).unwrap(), 114);
     }
 
     #[test]
@@ -63,6 +60,6 @@ mod tests {
         let input = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
-        assert_eq!(part2(input), 2);
+        assert_eq!(part2(input).unwrap(), 2);
     }
 }
@@ -1,39 +1,36 @@
+use crate::error::{parse_lines, Error, Result};
 use std::collections::HashMap;
-use std::error::Error;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let (first, second) = parse(input).unwrap();
-    first
+fn part1(input: &str) -> Result<usize> {
+    let (first, second) = parse(input)?;
+    Ok(first
         .iter()
         .zip(second.iter())
         .map(|(a, b)| a.abs_diff(*b))
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    let (first, second) = parse(input).unwrap();
+fn part2(input: &str) -> Result<usize> {
+    let (first, second) = parse(input)?;
     let counts = second.into_iter().fold(HashMap::new(), |mut acc, n| {
         *acc.entry(n).or_insert_with(|| 0) += 1;
         acc
     });
-    first.iter().map(|n| counts.get(n).unwrap_or(&0) * n).sum()
+    Ok(first.iter().map(|n| counts.get(n).unwrap_or(&0) * n).sum())
 }
 
-fn parse(input: &str) -> Result<(Vec<usize>, Vec<usize>), Box<dyn Error>> {
-    let (mut first, mut second): (Vec<usize>, Vec<usize>) = input
-        .lines()
-        .filter_map(|line| {
+fn parse(input: &str) -> Result<(Vec<usize>, Vec<usize>)> {
+    let (mut first, mut second): (Vec<usize>, Vec<usize>) =
+        parse_lines(input, |line| -> Result<(usize, usize)> {
             let mut parts = line.split_whitespace();
-            Some((
-                parts.next()?.parse::<usize>().ok()?,
-                parts.next()?.parse::<usize>().ok()?,
-            ))
-        })
+            match (parts.next(), parts.next()) {
+                (Some(a), Some(b)) => Ok((a.parse()?, b.parse()?)),
+                _ => Err(Error::parse("expected two numbers")),
+            }
+        })?
+        .into_iter()
         .unzip();
     first.sort_unstable();
     second.sort_unstable();
@@ -52,7 +49,7 @@ mod tests {
 1   3
 3   9
 3   3";
-        assert_eq!(part1(input), 11);
+        assert_eq!(part1(input).unwrap(), 11);
     }
     #[test]
     fn test_part2() {
@@ -62,6 +59,6 @@ mod tests {
 1   3
 3   9
 3   3";
-        assert_eq!(part2(input), 31);
+        assert_eq!(part2(input).unwrap(), 31);
     }
 }
@@ -1,32 +1,30 @@
+use crate::error::Result;
 use crate::util::coordinate::Coordinate;
 use crate::util::grid::Grid;
-use std::collections::HashSet;
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
-
-const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+crate::solution!(part1, part2, Grid::<i8>::from_str);
 
-fn part1(input: &str) -> usize {
-    let grid = Grid::<i8>::from_str(input).unwrap();
-    grid.find_all(&0)
+fn part1(input: &str) -> Result<usize> {
+    let grid = Grid::<i8>::from_str(input)?;
+    Ok(grid
+        .find_all(&0)
         .map(|start| {
-            find_destinations(&grid, start)
-                .into_iter()
-                .collect::<HashSet<_>>()
-                .len()
+            grid.flood_fill(&start, |&height, &next| next - height == 1)
+                .cells()
+                .iter()
+                .filter(|&&c| grid[c] == 9)
+                .count()
         })
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    let grid = Grid::<i8>::from_str(input).unwrap();
-    grid.find_all(&0)
+fn part2(input: &str) -> Result<usize> {
+    let grid = Grid::<i8>::from_str(input)?;
+    Ok(grid
+        .find_all(&0)
         .map(|start| find_destinations(&grid, start).len())
-        .sum()
+        .sum())
 }
 
 fn find_destinations(grid: &Grid<i8>, start: Coordinate) -> Vec<Coordinate> {
@@ -36,14 +34,7 @@ fn find_destinations(grid: &Grid<i8>, start: Coordinate) -> Vec<Coordinate> {
         match grid.get(&current) {
             Some(&9) => destinations.push(current),
             Some(&height) => {
-                for (dy, dx) in DIRECTIONS {
-                    let next_coord = Coordinate::new(current.y + dy, current.x + dx);
-                    if let Some(&next_height) = grid.get(&next_coord) {
-                        if next_height - height == 1 {
-                            stack.push(next_coord);
-                        }
-                    }
-                }
+                stack.extend(grid.neighbors4_where(&current, |&next| next - height == 1))
             }
             None => {}
         }
@@ -71,12 +62,12 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(SMALL), 1);
-        assert_eq!(part1(LARGE), 36);
+        assert_eq!(part1(SMALL).unwrap(), 1);
+        assert_eq!(part1(LARGE).unwrap(), 36);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(LARGE), 81);
+        assert_eq!(part2(LARGE).unwrap(), 81);
     }
 }
@@ -1,18 +1,16 @@
+use crate::error::Result;
 use std::collections::HashMap;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let stones = parse(input).unwrap();
-    observe_stones(stones, 25)
+fn part1(input: &str) -> Result<usize> {
+    let stones = parse(input)?;
+    Ok(observe_stones(stones, 25))
 }
 
-fn part2(input: &str) -> usize {
-    let stones = parse(input).unwrap();
-    observe_stones(stones, 75)
+fn part2(input: &str) -> Result<usize> {
+    let stones = parse(input)?;
+    Ok(observe_stones(stones, 75))
 }
 
 type MemoizationCache = HashMap<(usize, usize), usize>;
@@ -34,7 +32,7 @@ fn blink(stone: usize, remaining_blinks: usize, cache: &mut MemoizationCache) ->
     }
     let result = match stone {
         0 => blink(1, remaining_blinks - 1, cache),
-        stone if num_digits(stone) % 2 == 0 => {
+        stone if num_digits(stone).is_multiple_of(2) => {
             let (left, right) = split_number(stone);
             blink(left, remaining_blinks - 1, cache) + blink(right, remaining_blinks - 1, cache)
         }
@@ -53,10 +51,10 @@ fn split_number(num: usize) -> (usize, usize) {
     (num / divisor, num % divisor)
 }
 
-fn parse(input: &str) -> Result<Vec<usize>, std::num::ParseIntError> {
+fn parse(input: &str) -> Result<Vec<usize>> {
     input
         .split_whitespace()
-        .map(|c| c.parse::<usize>())
+        .map(|c| Ok(c.parse::<usize>()?))
         .collect()
 }
 
@@ -68,11 +66,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 55312);
+        assert_eq!(part1(INPUT).unwrap(), 55312);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 65601038650482);
+        assert_eq!(part2(INPUT).unwrap(), 65601038650482);
     }
 }
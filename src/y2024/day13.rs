@@ -1,3 +1,4 @@
+use crate::error::{Error, Result};
 use crate::util::coordinate::Coordinate;
 use crate::util::dir::Direction;
 use lazy_static::lazy_static;
@@ -11,37 +12,42 @@ lazy_static! {
 
 const EPSILON: f64 = 1e-9;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let machines = parse(input).unwrap();
-    machines
+fn part1(input: &str) -> Result<usize> {
+    let machines = parse(input)?;
+    Ok(machines
         .into_iter()
         .filter_map(|val| val.min_tokens())
         .filter(|&(a, b)| a <= 100 && b <= 100)
         .map(|(a, b)| a * 3 + b)
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    let mut machines = parse(input).unwrap();
+fn part2(input: &str) -> Result<usize> {
+    let mut machines = parse(input)?;
     for machine in machines.iter_mut() {
         machine.prize.x += 10000000000000;
         machine.prize.y += 10000000000000;
     }
-    machines
+    Ok(machines
         .into_iter()
         .filter_map(|val| val.min_tokens())
         .map(|(a, b)| a * 3 + b)
-        .sum()
+        .sum())
 }
 
-fn parse(input: &str) -> Result<Vec<Machine>, ParseMachineError> {
-    let section = input.split("\n\n");
-    section.into_iter().map(Machine::from_str).collect()
+/// Parses the machines, tagging errors with the first line of the machine they occurred in.
+fn parse(input: &str) -> Result<Vec<Machine>> {
+    let mut line = 1;
+    input
+        .split("\n\n")
+        .map(|section| {
+            let machine = Machine::from_str(section).map_err(|e| e.at_line(line));
+            line += section.lines().count() + 1;
+            machine
+        })
+        .collect()
 }
 
 #[derive(Debug)]
@@ -74,25 +80,26 @@ impl Machine {
     }
 }
 
-#[derive(Debug)]
-struct ParseMachineError {}
 impl FromStr for Machine {
-    type Err = ParseMachineError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let lines = s.lines().collect::<Vec<_>>();
+        if lines.len() < 3 {
+            return Err(Error::parse("expected two buttons and a prize"));
+        }
         let a: (usize, usize) = BUTTON
             .captures(lines[0])
             .and_then(|cap| Some((cap[1].parse().ok()?, cap[2].parse().ok()?)))
-            .ok_or(ParseMachineError {})?;
+            .ok_or_else(|| Error::parse(format!("invalid button '{}'", lines[0])))?;
         let b: (usize, usize) = BUTTON
             .captures(lines[1])
             .and_then(|cap| Some((cap[1].parse().ok()?, cap[2].parse().ok()?)))
-            .ok_or(ParseMachineError {})?;
+            .ok_or_else(|| Error::parse(format!("invalid button '{}'", lines[1])))?;
         let prize: (usize, usize) = PRIZE
             .captures(lines[2])
             .and_then(|cap| Some((cap[1].parse().ok()?, cap[2].parse().ok()?)))
-            .ok_or(ParseMachineError {})?;
+            .ok_or_else(|| Error::parse(format!("invalid prize '{}'", lines[2])))?;
         Ok(Self {
             a: Direction::new(a.1 as isize, a.0 as isize),
             b: Direction::new(b.1 as isize, b.0 as isize),
@@ -123,11 +130,20 @@ Prize: X=18641, Y=10279";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 480);
+        assert_eq!(part1(INPUT).unwrap(), 480);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 875318608908);
+        assert_eq!(part2(INPUT).unwrap(), 875318608908);
+    }
+
+    #[test]
+    fn test_invalid_machine() {
+        let input = "Button A: X+1, Y+2\nButton B: X+3, Y+4\nPrize: X=5, Y=6\n\nButton A: X+1\n";
+        assert_eq!(
+            part1(input).unwrap_err().to_string(),
+            "failed to parse line 5: expected two buttons and a prize"
+        );
     }
 }
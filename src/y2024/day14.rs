@@ -1,27 +1,26 @@
+use crate::error::{parse_lines, Error, Result};
+use crate::params::at_least;
 use crate::util::coordinate::Coordinate;
 use crate::util::dir::Direction;
 use crate::util::grid::Grid;
-use std::num::ParseIntError;
+use crate::util::wrapping_grid::WrappingGrid;
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input, 103, 101));
-    println!("Part 2: {}", part2(input, 103, 101));
-}
+crate::params!(
+    struct Params { y_len: usize = 103, x_len: usize = 101 }
+);
+crate::solution!(part1, part2; Params);
 
-fn part1(input: &str, y_len: usize, x_len: usize) -> usize {
-    let grid = Grid::new_with(y_len, x_len, '.');
-    let mut guards = input
-        .lines()
-        .map(Guard::from_str)
-        .collect::<Result<Vec<Guard>, ParseIntError>>()
-        .unwrap();
+fn part1(input: &str, params: &Params) -> Result<usize> {
+    let grid = floor(params)?;
+    let (y_len, x_len) = (params.y_len, params.x_len);
+    let mut guards = parse_lines(input, Guard::from_str)?;
     guards.iter_mut().for_each(|g| {
         simulate(g, 100, &grid);
     });
     let mid_y = (y_len / 2) as isize;
     let mid_x = (x_len / 2) as isize;
-    guards
+    Ok(guards
         .into_iter()
         .map(|g| g.pos)
         .fold([0; 4], |mut quadrants, pos| {
@@ -31,16 +30,12 @@ fn part1(input: &str, y_len: usize, x_len: usize) -> usize {
             quadrants
         })
         .iter()
-        .product()
+        .product())
 }
 
-fn part2(input: &str, y_len: usize, x_len: usize) -> usize {
-    let mut grid = Grid::new_with(y_len, x_len, '.');
-    let mut guards = input
-        .lines()
-        .map(Guard::from_str)
-        .collect::<Result<Vec<Guard>, ParseIntError>>()
-        .unwrap();
+fn part2(input: &str, params: &Params) -> Result<usize> {
+    let grid = floor(params)?;
+    let mut guards = parse_lines(input, Guard::from_str)?;
     let mut second = 1;
     loop {
         guards.iter_mut().for_each(|g| {
@@ -51,21 +46,18 @@ fn part2(input: &str, y_len: usize, x_len: usize) -> usize {
         }
         second += 1;
     }
-    guards.iter().for_each(|g| {
-        grid.replace(&g.pos, '#');
-    });
-    println!("{}", grid);
-    second
+    Ok(second)
+}
+
+/// The floor the guards wrap around on, which needs at least one tile.
+fn floor(params: &Params) -> Result<WrappingGrid<char>> {
+    let y_len = at_least("y_len", params.y_len, 1)?;
+    let x_len = at_least("x_len", params.x_len, 1)?;
+    Ok(WrappingGrid::new(Grid::new_with(y_len, x_len, '.')))
 }
 
-fn simulate(guard: &mut Guard, seconds: usize, grid: &Grid<char>) {
-    let new_y = ((guard.pos.y + guard.vel.dy * seconds as isize) % grid.y_len() as isize
-        + grid.y_len() as isize)
-        % grid.y_len() as isize;
-    let new_x = ((guard.pos.x + guard.vel.dx * seconds as isize) % grid.x_len() as isize
-        + grid.x_len() as isize)
-        % grid.x_len() as isize;
-    guard.pos = Coordinate::new(new_y, new_x);
+fn simulate(guard: &mut Guard, seconds: usize, grid: &WrappingGrid<char>) {
+    guard.pos = grid.offset(&guard.pos, &guard.vel, seconds as isize);
 }
 
 fn determine_quadrant(mid_y: isize, mid_x: isize, pos: &Coordinate) -> Option<usize> {
@@ -104,20 +96,27 @@ struct Guard {
 }
 
 impl FromStr for Guard {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split_whitespace().collect::<Vec<_>>();
-        let raw_pos = parts[0].replace("p=", "");
-        let raw_pos = raw_pos.split(",").collect::<Vec<_>>();
-        let raw_vel = parts[1].replace("v=", "");
-        let raw_vel = raw_vel.split(",").collect::<Vec<_>>();
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (raw_pos, raw_vel) = s
+            .split_once(" ")
+            .ok_or_else(|| Error::parse(format!("invalid guard '{}'", s)))?;
+        let (pos_x, pos_y) = split_pair(raw_pos, "p=")
+            .ok_or_else(|| Error::parse(format!("invalid position '{}'", raw_pos)))?;
+        let (vel_x, vel_y) = split_pair(raw_vel, "v=")
+            .ok_or_else(|| Error::parse(format!("invalid velocity '{}'", raw_vel)))?;
         Ok(Self {
-            pos: Coordinate::new(raw_pos[1].parse()?, raw_pos[0].parse()?),
-            vel: Direction::new(raw_vel[1].parse()?, raw_vel[0].parse()?),
+            pos: Coordinate::new(pos_y.parse()?, pos_x.parse()?),
+            vel: Direction::new(vel_y.parse()?, vel_x.parse()?),
         })
     }
 }
+
+fn split_pair<'a>(raw: &'a str, prefix: &str) -> Option<(&'a str, &'a str)> {
+    raw.strip_prefix(prefix)?.split_once(",")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +136,28 @@ p=9,5 v=-3,-3";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT, 7, 11), 12)
+        assert_eq!(
+            part1(
+                INPUT,
+                &Params {
+                    y_len: 7,
+                    x_len: 11
+                }
+            )
+            .unwrap(),
+            12
+        )
+    }
+
+    #[test]
+    fn test_empty_floor() {
+        let params = Params {
+            y_len: 0,
+            x_len: 11,
+        };
+        assert_eq!(
+            part2(INPUT, &params).unwrap_err().to_string(),
+            "Invalid value for parameter y_len: 0 (must be at least 1)"
+        );
     }
 }
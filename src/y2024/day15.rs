@@ -1,28 +1,25 @@
+use crate::error::{Error, Result};
 use crate::util::coordinate::Coordinate;
 use crate::util::dir::Direction;
 use crate::util::grid::Grid;
-use std::collections::VecDeque;
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let (mut map, mut moves) = parse(input);
-    while let Some(movement) = moves.pop_front() {
+fn part1(input: &str) -> Result<usize> {
+    let (mut map, moves) = parse(input)?;
+    for movement in moves {
         map.move_robot(movement);
     }
-    map.score()
+    Ok(map.score())
 }
 
-fn part2(input: &str) -> usize {
-    let (mut map, mut moves) = parse(&double_grid(input));
-    while let Some(movement) = moves.pop_front() {
+fn part2(input: &str) -> Result<usize> {
+    let (mut map, moves) = parse(&double_grid(input))?;
+    for movement in moves {
         map.move_robot(movement);
     }
-    map.score()
+    Ok(map.score())
 }
 
 #[derive(Debug)]
@@ -32,14 +29,7 @@ struct WarehouseMap {
 }
 
 impl WarehouseMap {
-    pub fn move_robot(&mut self, dir: char) {
-        let dir = match dir {
-            '^' => Direction::UP,
-            '>' => Direction::RIGHT,
-            'v' => Direction::DOWN,
-            '<' => Direction::LEFT,
-            _ => unreachable!(),
-        };
+    pub fn move_robot(&mut self, dir: Direction) {
         let current = self.robot;
         if self.try_move_thing(&current, &dir, true) {
             self.robot = Coordinate::new(current.y + dir.dy, current.x + dir.dx);
@@ -51,7 +41,8 @@ impl WarehouseMap {
         let move_successful = match self.grid.get(&next) {
             Some('.') => true,
             Some('O') => self.try_move_thing(&next, dir, apply),
-            Some('#') => false,
+            // Without a wall around the map, the edge blocks moves just the same
+            Some('#') | None => false,
             Some('[') => match dir {
                 &Direction::LEFT | &Direction::RIGHT => self.try_move_thing(&next, dir, true),
                 _ => self.try_move_big_box(&'[', &next, dir, apply),
@@ -96,22 +87,40 @@ impl WarehouseMap {
     }
 }
 
-impl From<&str> for WarehouseMap {
-    fn from(value: &str) -> Self {
-        let grid = Grid::<char>::from_str(value.trim()).unwrap();
-        let robot = grid.find(&'@').unwrap();
-        Self { grid, robot }
+impl FromStr for WarehouseMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let grid = Grid::parse_with(s.trim(), |c| match c {
+            '#' | '.' | 'O' | '[' | ']' | '@' => Ok(c),
+            _ => Err(()),
+        })?;
+        let robot = match grid.find_all(&'@').collect::<Vec<_>>()[..] {
+            [robot] => robot,
+            [] => return Err(Error::parse("no robot")),
+            _ => return Err(Error::parse("more than one robot")),
+        };
+        Ok(Self { grid, robot })
     }
 }
 
-fn parse(input: &str) -> (WarehouseMap, VecDeque<char>) {
-    let parts = input.split("\n\n").collect::<Vec<_>>();
-    let map = WarehouseMap::from(parts[0]);
-    let moves = parts[1]
+fn parse(input: &str) -> Result<(WarehouseMap, Vec<Direction>)> {
+    let (map, moves) = input
+        .split_once("\n\n")
+        .ok_or_else(|| Error::parse("missing move list"))?;
+    let map = WarehouseMap::from_str(map)?;
+    let moves = moves
         .lines()
         .flat_map(|l| l.chars())
-        .collect::<VecDeque<_>>();
-    (map, moves)
+        .map(|c| match c {
+            '^' => Ok(Direction::UP),
+            '>' => Ok(Direction::RIGHT),
+            'v' => Ok(Direction::DOWN),
+            '<' => Ok(Direction::LEFT),
+            _ => Err(Error::parse(format!("invalid move {:?}", c))),
+        })
+        .collect::<Result<_>>()?;
+    Ok((map, moves))
 }
 
 fn double_grid(map_raw: &str) -> String {
@@ -176,13 +185,30 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(SMALL), 2028);
-        assert_eq!(part1(LARGE), 10092);
+        assert_eq!(part1(SMALL).unwrap(), 2028);
+        assert_eq!(part1(LARGE).unwrap(), 10092);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(SMALL_ALT), 618);
-        assert_eq!(part2(LARGE), 9021);
+        assert_eq!(part2(SMALL_ALT).unwrap(), 618);
+        assert_eq!(part2(LARGE).unwrap(), 9021);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(parse("#@.#"), Err(Error::Parse { .. })));
+        assert!(matches!(parse("#..#\n\n<>"), Err(Error::Parse { .. })));
+        assert!(matches!(parse("#@.#\n\n<x"), Err(Error::Parse { .. })));
+        assert_eq!(
+            parse("#@@#\n\n<").unwrap_err().to_string(),
+            "failed to parse input: more than one robot"
+        );
+    }
+
+    #[test]
+    fn test_no_border() {
+        assert_eq!(part1(".O@\n\n>>^v").unwrap(), 1);
+        assert_eq!(part2("O@\n\n<<<<").unwrap(), 0);
     }
 }
@@ -1,89 +1,73 @@
+use crate::error::{Error, Result};
 use crate::util::coordinate::Coordinate;
 use crate::util::dir::Direction;
 use crate::util::grid::Grid;
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::str::FromStr;
+use crate::util::search::{self, Search};
+use std::collections::HashSet;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, Grid::<Tile>::parse_cells);
 
-fn part1(input: &str) -> usize {
-    let maze = Grid::<char>::from_str(input).unwrap();
-    let paths = find_best_paths(&maze);
-    paths.first().map(|(score, _)| *score).unwrap()
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tile {
+    Wall,
+    Open,
+    Start,
+    End,
 }
 
-fn part2(input: &str) -> usize {
-    let maze = Grid::<char>::from_str(input).unwrap();
-    let paths = find_best_paths(&maze);
-    paths
-        .into_iter()
-        .flat_map(|(_, path)| path)
-        .collect::<HashSet<_>>()
-        .len()
-}
+impl TryFrom<char> for Tile {
+    type Error = char;
 
-#[derive(Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    dir: Direction,
-    path: Vec<Coordinate>,
+    fn try_from(c: char) -> std::result::Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Open),
+            'S' => Ok(Tile::Start),
+            'E' => Ok(Tile::End),
+            _ => Err(c),
+        }
+    }
 }
 
-fn find_best_paths(maze: &Grid<char>) -> Vec<(usize, Vec<Coordinate>)> {
-    let start = maze.find(&'S').unwrap();
-    let mut distances: HashMap<(Coordinate, Direction), usize> = HashMap::new();
-    let mut queue = VecDeque::new();
-    queue.push_back(State {
-        cost: 0usize,
-        dir: Direction::RIGHT,
-        path: vec![start],
-    });
-    distances.insert((start, Direction::RIGHT), 0);
-
-    let mut lowest_cost = usize::MAX;
-    let mut best_paths = Vec::<(usize, Vec<Coordinate>)>::new();
-    while let Some(state) = queue.pop_front() {
-        let pos = state.path.last().unwrap();
-        let val = maze.get(pos);
-        if val == Some(&'E') {
-            if state.cost < lowest_cost {
-                best_paths.clear();
-            }
-            if state.cost <= lowest_cost {
-                best_paths.push((state.cost, state.path));
-                lowest_cost = state.cost;
-            }
-            continue;
-        }
-        if val == Some(&'#')
-            || state.cost > *distances.get(&(*pos, state.dir)).unwrap_or(&usize::MAX)
-        {
-            continue;
-        }
-        distances.insert((*pos, state.dir), state.cost);
+fn part1(input: &str) -> Result<usize> {
+    let maze = Grid::parse_cells(input)?;
+    find_best_paths(&maze)?
+        .cost()
+        .ok_or_else(|| Error::NoAnswer("no path to the end tile".to_string()))
+}
 
-        [
-            state.dir,
-            state.dir.rotated_left(),
-            state.dir.rotated_right(),
-        ]
+fn part2(input: &str) -> Result<usize> {
+    let maze = Grid::parse_cells(input)?;
+    Ok(find_best_paths(&maze)?
+        .on_best_paths()
         .into_iter()
-        .for_each(|dir| {
-            let rotation_penalty = if dir == state.dir { 0 } else { 1000 };
-            let mut path = state.path.clone();
-            let next = pos.offset(&dir);
-            path.push(next);
-            queue.push_back(State {
-                cost: state.cost + rotation_penalty + 1,
-                dir,
-                path,
-            });
-        });
-    }
-    best_paths
+        .map(|(pos, _)| pos)
+        .collect::<HashSet<_>>()
+        .len())
+}
+
+/// Searches states of a position and facing, where stepping forward costs 1 and turning in place 1000.
+fn find_best_paths(maze: &Grid<Tile>) -> Result<Search<(Coordinate, Direction)>> {
+    let start = maze
+        .find(&Tile::Start)
+        .ok_or_else(|| Error::parse("no start tile"))?;
+    Ok(search::dijkstra(
+        (start, Direction::RIGHT),
+        |&(pos, dir)| {
+            let forward = pos.offset(&dir);
+            let step = match maze.get(&forward) {
+                Some(Tile::Wall) | None => None,
+                Some(_) => Some(((forward, dir), 1)),
+            };
+            [
+                ((pos, dir.rotated_left()), 1000),
+                ((pos, dir.rotated_right()), 1000),
+            ]
+            .into_iter()
+            .chain(step)
+        },
+        |(pos, _)| maze.get(pos) == Some(&Tile::End),
+    ))
 }
 
 #[cfg(test)]
@@ -126,12 +110,12 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(FIRST), 7036);
+        assert_eq!(part1(FIRST).unwrap(), 7036);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(FIRST), 45);
-        assert_eq!(part2(SECOND), 64);
+        assert_eq!(part2(FIRST).unwrap(), 45);
+        assert_eq!(part2(SECOND).unwrap(), 64);
     }
 }
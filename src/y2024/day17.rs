@@ -1,23 +1,21 @@
-use std::num::ParseIntError;
+use crate::error::{parse_lines, Error, Result};
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, Computer::from_str);
 
-fn part1(input: &str) -> String {
-    let mut comp = Computer::from_str(input).unwrap();
+fn part1(input: &str) -> Result<String> {
+    let mut comp = Computer::from_str(input)?;
     while comp.run().is_some() {}
-    comp.out
+    Ok(comp
+        .out
         .into_iter()
         .map(|x| x.to_string())
         .collect::<Vec<_>>()
-        .join(",")
+        .join(","))
 }
 
-fn part2(input: &str) -> usize {
-    let comp = Computer::from_str(input).unwrap();
+fn part2(input: &str) -> Result<usize> {
+    let comp = Computer::from_str(input)?;
     let initial_a = comp.registers[0];
     let expected_out = comp.instructions.to_vec();
 
@@ -44,7 +42,7 @@ fn part2(input: &str) -> usize {
             candidate += 1;
         }
     }
-    candidate
+    Ok(candidate)
 }
 
 #[derive(Debug, Clone)]
@@ -56,19 +54,29 @@ struct Computer {
 }
 
 impl FromStr for Computer {
-    type Err = ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.split("\n\n").collect::<Vec<_>>();
-        let registers = s[0].lines().collect::<Vec<_>>();
-        let a = registers[0].replace("Register A: ", "").parse()?;
-        let b = registers[1].replace("Register B: ", "").parse()?;
-        let c = registers[2].replace("Register C: ", "").parse()?;
-        let instructions = s[1]
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (registers, program) = s
+            .split_once("\n\n")
+            .ok_or_else(|| Error::parse("missing program"))?;
+        let registers = parse_lines(registers, |line| {
+            line.split_once(": ")
+                .ok_or_else(|| Error::parse(format!("invalid register '{}'", line)))
+                .and_then(|(_, value)| Ok(value.parse::<usize>()?))
+        })?;
+        let [a, b, c] = registers[..] else {
+            return Err(Error::parse("expected three registers"));
+        };
+        let instructions = program
+            .trim()
             .replace("Program: ", "")
             .split(",")
             .map(|x| x.parse::<usize>())
-            .collect::<Result<Vec<usize>, _>>()?;
+            .collect::<std::result::Result<Vec<usize>, _>>()?;
+        if let Some(x) = instructions.iter().find(|&&x| x > 7) {
+            return Err(Error::parse(format!("{} is not a 3-bit number", x)));
+        }
         Ok(Self {
             registers: [a, b, c],
             instructions,
@@ -180,7 +188,7 @@ Register B: 0
 Register C: 0
 
 Program: 0,1,5,4,3,0";
-        assert_eq!(part1(input), "4,6,3,5,6,3,5,2,1,0");
+        assert_eq!(part1(input).unwrap(), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
@@ -190,6 +198,6 @@ Register B: 0
 Register C: 0
 
 Program: 0,3,5,4,3,0";
-        assert_eq!(part2(input), 117440);
+        assert_eq!(part2(input).unwrap(), 117440);
     }
 }
@@ -1,88 +1,72 @@
+use crate::error::{parse_lines, Error, Result};
 use crate::util::coordinate::Coordinate;
-use crate::util::dir::Direction;
 use crate::util::grid::Grid;
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::util::search::{self, Search};
+use std::collections::HashSet;
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::UP,
-    Direction::RIGHT,
-    Direction::DOWN,
-    Direction::LEFT,
-];
+crate::params!(
+    struct Params { size: usize = 71, sim_len: usize = 1024 }
+);
+crate::solution!(part1, part2, parse; Params);
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input, 71, 1024));
-    println!("Part 2: {:?}", part2(input, 71));
-}
-
-fn part1(input: &str, size: usize, sim_len: usize) -> usize {
-    let bytes = parse(input);
+fn part1(input: &str, params: &Params) -> Result<usize> {
+    let (size, sim_len) = (params.size, params.sim_len);
+    let bytes = parse(input)?;
     let mut grid = Grid::<char>::new_with(size, size, '.');
     bytes.into_iter().take(sim_len).for_each(|byte_pos| {
         grid.replace(&byte_pos, '#');
     });
     let target = Coordinate::new(size as isize - 1, size as isize - 1);
-    get_shortest_path(&grid, &target).unwrap().len()
+    search_exit(&grid, &target)
+        .and_then(|search| search.cost())
+        .ok_or_else(|| Error::NoAnswer("no path to the exit".to_string()))
 }
 
-fn part2(input: &str, size: usize) -> Option<Coordinate> {
-    let bytes = parse(input);
+fn part2(input: &str, params: &Params) -> Result<Coordinate> {
+    let size = params.size;
+    let bytes = parse(input)?;
     let mut grid = Grid::<char>::new_with(size, size, '.');
     let target = Coordinate::new(size as isize - 1, size as isize - 1);
-    let mut shortest_path = get_shortest_path(&grid, &target)?;
+    let no_answer = || Error::NoAnswer("no byte blocks the exit".to_string());
+    let mut shortest_path = get_shortest_path(&grid, &target).ok_or_else(no_answer)?;
     for byte in bytes {
         grid.replace(&byte, '#');
         if shortest_path.contains(&byte) {
             match get_shortest_path(&grid, &target) {
                 Some(new_path) => shortest_path = new_path,
-                None => return Some(byte),
+                None => return Ok(byte),
             }
         }
     }
-    None
+    Err(no_answer())
 }
 
-fn get_shortest_path(grid: &Grid<char>, target: &Coordinate) -> Option<HashSet<Coordinate>> {
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-    let mut predecessors = HashMap::new();
-    queue.push_back(Coordinate::new(0, 0));
-    visited.insert(Coordinate::new(0, 0));
-    while let Some(current) = queue.pop_front() {
-        if current == *target {
-            let mut path = HashSet::new();
-            let mut current = current;
-            while let Some(prev) = predecessors.get(&current) {
-                path.insert(current);
-                current = *prev;
-            }
-            return Some(path);
-        }
-        match grid.get(&current) {
-            Some('#') | None => continue,
-            _ => {}
-        }
-        for dir in DIRECTIONS.iter() {
-            let next = current.offset(dir);
-            if visited.contains(&next) {
-                continue;
-            }
-            visited.insert(next);
-            predecessors.insert(next, current);
-            queue.push_back(next);
-        }
+/// Searches from the top left corner, or returns `None` if a byte fell on it.
+fn search_exit(grid: &Grid<char>, target: &Coordinate) -> Option<Search<Coordinate>> {
+    let start = Coordinate::new(0, 0);
+    if grid.get(&start) == Some(&'#') {
+        return None;
     }
-    None
+    Some(search::bfs(
+        start,
+        |current| grid.neighbors4_where(current, |&v| v != '#'),
+        |current| current == target,
+    ))
+}
+
+/// Positions on one shortest path to the exit, start and exit included, since a byte on either blocks it.
+fn get_shortest_path(grid: &Grid<char>, target: &Coordinate) -> Option<HashSet<Coordinate>> {
+    let path = search_exit(grid, target)?.path(target)?;
+    Some(path.into_iter().collect())
 }
 
-fn parse(input: &str) -> Vec<Coordinate> {
-    input
-        .lines()
-        .map(|line| {
-            let parts = line.split(",").collect::<Vec<_>>();
-            Coordinate::new(parts[1].parse().unwrap(), parts[0].parse().unwrap())
-        })
-        .collect()
+fn parse(input: &str) -> Result<Vec<Coordinate>> {
+    parse_lines(input, |line| {
+        let (x, y) = line
+            .split_once(",")
+            .ok_or_else(|| Error::parse(format!("invalid byte position '{}'", line)))?;
+        Ok::<_, Error>(Coordinate::new(y.parse()?, x.parse()?))
+    })
 }
 
 #[cfg(test)]
@@ -117,11 +101,41 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT, 7, 12), 22);
+        assert_eq!(
+            part1(
+                INPUT,
+                &Params {
+                    size: 7,
+                    sim_len: 12
+                }
+            )
+            .unwrap(),
+            22
+        );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT, 7), Some(Coordinate::new(1, 6)));
+        assert_eq!(
+            part2(
+                INPUT,
+                &Params {
+                    size: 7,
+                    ..Params::default()
+                }
+            )
+            .unwrap(),
+            Coordinate::new(1, 6)
+        );
+    }
+
+    #[test]
+    fn test_blocked_start() {
+        let params = Params {
+            size: 7,
+            sim_len: 1,
+        };
+        assert!(matches!(part1("0,0\n", &params), Err(Error::NoAnswer(_))));
+        assert_eq!(part2("0,0\n6,6\n", &params).unwrap(), Coordinate::new(0, 0));
     }
 }
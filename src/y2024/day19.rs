@@ -1,27 +1,25 @@
+use crate::error::{Error, Result};
 use std::collections::{HashMap, HashSet};
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let (towels, designs) = parse(input);
+fn part1(input: &str) -> Result<usize> {
+    let (towels, designs) = parse(input)?;
     let towel_sizes = towels.iter().map(|t| t.len()).collect::<HashSet<usize>>();
     let mut cache: HashMap<String, usize> = HashMap::new();
-    designs
+    Ok(designs
         .into_iter()
         .filter(|design| num_possible(design, &towel_sizes, &towels, &mut cache) > 0)
-        .count()
+        .count())
 }
-fn part2(input: &str) -> usize {
-    let (towels, designs) = parse(input);
+fn part2(input: &str) -> Result<usize> {
+    let (towels, designs) = parse(input)?;
     let towel_sizes = towels.iter().map(|t| t.len()).collect::<HashSet<usize>>();
     let mut cache: HashMap<String, usize> = HashMap::new();
-    designs
+    Ok(designs
         .into_iter()
         .map(|design| num_possible(&design, &towel_sizes, &towels, &mut cache))
-        .sum()
+        .sum())
 }
 
 fn num_possible(
@@ -47,15 +45,18 @@ fn num_possible(
     num_possible
 }
 
-fn parse(input: &str) -> (HashSet<String>, Vec<String>) {
-    let sections = input.split("\n\n").collect::<Vec<_>>();
-    (
-        sections[0]
-            .split(",")
-            .map(|s| s.trim().to_owned())
-            .collect(),
-        sections[1].lines().map(|s| s.to_owned()).collect(),
-    )
+fn parse(input: &str) -> Result<(HashSet<String>, Vec<String>)> {
+    let (towels, designs) = input
+        .split_once("\n\n")
+        .ok_or_else(|| Error::parse("expected towels and designs separated by a blank line"))?;
+    // Designs are sliced by byte length
+    if !input.is_ascii() {
+        return Err(Error::parse("expected only ASCII stripe colors"));
+    }
+    Ok((
+        towels.split(',').map(|s| s.trim().to_owned()).collect(),
+        designs.lines().map(|s| s.to_owned()).collect(),
+    ))
 }
 
 #[cfg(test)]
@@ -74,11 +75,11 @@ brgr
 bbrgwb";
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 6);
+        assert_eq!(part1(INPUT).unwrap(), 6);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 16);
+        assert_eq!(part2(INPUT).unwrap(), 16);
     }
 }
@@ -1,19 +1,20 @@
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+use crate::error::{parse_lines, Result};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    parse(input)
+fn part1(input: &str) -> Result<usize> {
+    Ok(parse(input)?
         .into_iter()
         .filter(|report| report.is_safe())
-        .count()
+        .count())
 }
-fn part2(input: &str) -> usize {
-    parse(input)
+fn part2(input: &str) -> Result<usize> {
+    Ok(parse(input)?
         .into_iter()
         .filter(|report| report.is_safe_problem_dampened())
-        .count()
+        .count())
 }
 
 struct Report {
@@ -42,14 +43,16 @@ impl Report {
     }
 }
 
-impl From<&str> for Report {
-    fn from(value: &str) -> Self {
-        Self {
-            levels: value
+impl FromStr for Report {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Self {
+            levels: s
                 .split_whitespace()
-                .map(|v| v.parse().unwrap())
-                .collect(),
-        }
+                .map(|v| v.parse())
+                .collect::<std::result::Result<_, _>>()?,
+        })
     }
 }
 
@@ -64,8 +67,8 @@ fn reversed(report: &[usize]) -> Vec<usize> {
     report.iter().rev().copied().collect()
 }
 
-fn parse(input: &str) -> Vec<Report> {
-    input.lines().map(Report::from).collect()
+fn parse(input: &str) -> Result<Vec<Report>> {
+    parse_lines(input, Report::from_str)
 }
 
 #[cfg(test)]
@@ -80,7 +83,7 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        assert_eq!(part1(input), 2);
+        assert_eq!(part1(input).unwrap(), 2);
     }
 
     #[test]
@@ -91,6 +94,6 @@ mod tests {
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9";
-        assert_eq!(part2(input), 4);
+        assert_eq!(part2(input).unwrap(), 4);
     }
 }
@@ -1,35 +1,39 @@
+use crate::error::{Error, Result};
 use crate::util::coordinate::Coordinate;
 use crate::util::dir::Direction;
 use crate::util::grid::Grid;
-use std::collections::{HashMap, VecDeque};
+use crate::util::search;
+use std::collections::HashMap;
 use std::str::FromStr;
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::UP,
-    Direction::RIGHT,
-    Direction::DOWN,
-    Direction::LEFT,
-];
+crate::params!(
+    struct Params { min_cost_reduction: usize = 100 }
+);
+crate::solution!(part1, part2, Grid::<char>::from_str; Params);
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input, 100));
-    println!("Part 2: {}", part2(input, 100));
+fn part1(input: &str, params: &Params) -> Result<usize> {
+    let track = Grid::<char>::from_str(input)?;
+    Ok(identify_cheats(&track, 2, params.min_cost_reduction)?.len())
 }
 
-fn part1(input: &str, min_cost_reduction: usize) -> usize {
-    let track = Grid::<char>::from_str(input).unwrap();
-    identify_cheats(&track, 2, min_cost_reduction).len()
+fn part2(input: &str, params: &Params) -> Result<usize> {
+    let track = Grid::<char>::from_str(input)?;
+    Ok(identify_cheats(&track, 20, params.min_cost_reduction)?.len())
 }
 
-fn part2(input: &str, min_cost_reduction: usize) -> usize {
-    let track = Grid::<char>::from_str(input).unwrap();
-    identify_cheats(&track, 20, min_cost_reduction).len()
-}
-
-fn identify_cheats(track: &Grid<char>, cheat_len: isize, min_cost_reduction: usize) -> Vec<usize> {
-    let end = track.find(&'E').unwrap();
-    let start = track.find(&'S').unwrap();
-    let path = path_distances(track, &start, &end).unwrap();
+fn identify_cheats(
+    track: &Grid<char>,
+    cheat_len: isize,
+    min_cost_reduction: usize,
+) -> Result<Vec<usize>> {
+    let end = track
+        .find(&'E')
+        .ok_or_else(|| Error::parse("no end tile"))?;
+    let start = track
+        .find(&'S')
+        .ok_or_else(|| Error::parse("no start tile"))?;
+    let path = path_distances(track, &start, &end)
+        .ok_or_else(|| Error::NoAnswer("no path from the start to the end".to_string()))?;
     let cheat_positions = manhattan_destinations(cheat_len);
     let mut cheats = Vec::new();
     for (coord, cost) in path.iter() {
@@ -37,17 +41,16 @@ fn identify_cheats(track: &Grid<char>, cheat_len: isize, min_cost_reduction: usi
             continue;
         }
         for (cheat_dir, len) in cheat_positions.iter() {
-            let dst_coord = coord.offset(cheat_dir);
-            if !path.contains_key(&dst_coord) {
+            let Some(dst_cost) = path.get(&coord.offset(cheat_dir)) else {
                 continue;
-            }
-            let cost_reduction = path.get(&dst_coord).unwrap().saturating_sub(cost + len);
+            };
+            let cost_reduction = dst_cost.saturating_sub(cost + len);
             if cost_reduction >= min_cost_reduction {
                 cheats.push(cost_reduction);
             }
         }
     }
-    cheats
+    Ok(cheats)
 }
 
 fn manhattan_destinations(max_distance: isize) -> Vec<(Direction, usize)> {
@@ -75,24 +78,13 @@ fn path_distances(
     start: &Coordinate,
     end: &Coordinate,
 ) -> Option<HashMap<Coordinate, usize>> {
-    let mut dist = HashMap::new();
-    let mut queue = VecDeque::new();
-    dist.insert(*start, 0);
-    queue.push_back((*start, 0));
-    while let Some((position, cost)) = queue.pop_front() {
-        if position == *end {
-            return Some(dist);
-        }
-        for dir in &DIRECTIONS {
-            let next = (position.offset(dir), cost + 1);
-            let next_val = grid.get(&next.0);
-            if !dist.contains_key(&next.0) && next_val.is_some() && next_val != Some(&'#') {
-                dist.insert(next.0, cost + 1);
-                queue.push_back(next);
-            }
-        }
-    }
-    None
+    let search = search::bfs(
+        *start,
+        |position| grid.neighbors4_where(position, |&value| value != '#'),
+        |position| position == end,
+    );
+    search.cost()?;
+    Some(search.into_distances())
 }
 
 #[cfg(test)]
@@ -116,7 +108,16 @@ mod tests {
 #.#.#.#.#.#.###
 #...#...#...###
 ###############";
-        assert_eq!(part1(input, 2), 44);
+        assert_eq!(
+            part1(
+                input,
+                &Params {
+                    min_cost_reduction: 2
+                }
+            )
+            .unwrap(),
+            44
+        );
     }
 
     #[test]
@@ -136,6 +137,15 @@ mod tests {
 #.#.#.#.#.#.###
 #...#...#...###
 ###############";
-        assert_eq!(part2(input, 50), 285);
+        assert_eq!(
+            part2(
+                input,
+                &Params {
+                    min_cost_reduction: 50
+                }
+            )
+            .unwrap(),
+            285
+        );
     }
 }
@@ -1,22 +1,12 @@
+use crate::error::{parse_lines, Error, Result};
 use crate::util::coordinate::Coordinate;
-use crate::util::dir::Direction;
 use crate::util::grid::Grid;
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::str::FromStr;
+use crate::util::search;
+use std::collections::HashMap;
 
-const DIRECTIONS: [Direction; 4] = [
-    Direction::UP,
-    Direction::RIGHT,
-    Direction::DOWN,
-    Direction::LEFT,
-];
+crate::solution!(part1, part2, parse);
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
-
-fn part1(input: &str) -> usize {
+fn part1(input: &str) -> Result<usize> {
     let numpad = ButtonPanel::numpad();
     let keypad = ButtonPanel::keypad();
     let mut panels = (0..2).map(|_| &keypad).collect::<Vec<_>>();
@@ -24,7 +14,7 @@ fn part1(input: &str) -> usize {
     total_complexity(input, &panels)
 }
 
-fn part2(input: &str) -> usize {
+fn part2(input: &str) -> Result<usize> {
     let numpad = ButtonPanel::numpad();
     let keypad = ButtonPanel::keypad();
     let mut panels = (0..25).map(|_| &keypad).collect::<Vec<_>>();
@@ -32,15 +22,25 @@ fn part2(input: &str) -> usize {
     total_complexity(input, &panels)
 }
 
-fn total_complexity(input: &str, panels: &Vec<&ButtonPanel>) -> usize {
+fn total_complexity(input: &str, panels: &Vec<&ButtonPanel>) -> Result<usize> {
     let mut cache = HashMap::new();
-    input
-        .lines()
-        .map(|line| {
-            min_sequence_of_word(panels, line.chars().collect(), panels.len() - 1, &mut cache)
-                * line.trim_end_matches("A").parse::<usize>().unwrap()
+    Ok(parse(input)?
+        .into_iter()
+        .map(|(code, value)| {
+            min_sequence_of_word(panels, code.chars().collect(), panels.len() - 1, &mut cache)
+                * value
         })
-        .sum()
+        .sum())
+}
+
+/// Every door code with its numeric part.
+fn parse(input: &str) -> Result<Vec<(String, usize)>> {
+    parse_lines(input, |line| -> Result<_> {
+        if line.is_empty() || !line.chars().all(|c| c.is_ascii_digit() || c == 'A') {
+            return Err(Error::parse(format!("invalid code '{}'", line)));
+        }
+        Ok((line.to_owned(), line.trim_end_matches('A').parse()?))
+    })
 }
 
 fn min_sequence_of_word(
@@ -92,11 +92,13 @@ impl ButtonPanel {
         ButtonPanel::new(".^A\n<v>")
     }
     fn new(keys: &str) -> Self {
-        let keys = Grid::from_str(keys).unwrap();
+        let keys = Grid::new(keys.lines().map(|row| row.chars().collect()).collect());
         Self { keys }
     }
     pub fn path_between(&self, source: char, key: char) -> Vec<Vec<char>> {
-        let source = self.keys.find(&source).unwrap();
+        let Some(source) = self.keys.find(&source) else {
+            return Vec::new();
+        };
         find_paths(&self.keys, &source, key)
             .into_iter()
             .map(to_key_presses)
@@ -119,36 +121,16 @@ fn to_key_presses(path: Vec<Coordinate>) -> Vec<char> {
 }
 
 fn find_paths(grid: &Grid<char>, source: &Coordinate, target: char) -> Vec<Vec<Coordinate>> {
-    let mut paths: Vec<Vec<Coordinate>> = Vec::new();
-    let mut visited = HashSet::new();
-    let mut stack = VecDeque::new();
-    stack.push_back(vec![*source]);
-    while let Some(current_path) = stack.pop_front() {
-        let last = current_path.last().unwrap();
-        visited.insert(*last);
-        match grid.get(last) {
-            Some(&key) if key == target => {
-                if !paths.is_empty() && current_path.len() > paths[0].len() {
-                    continue;
-                }
-                paths.push(current_path);
-            }
-            Some('.') | None => {
-                continue;
-            }
-            _ => {
-                for dir in DIRECTIONS.iter() {
-                    let next = last.offset(dir);
-                    if !visited.contains(&next) {
-                        let mut new_path = current_path.clone();
-                        new_path.push(next);
-                        stack.push_back(new_path);
-                    }
-                }
-            }
-        }
-    }
-    paths
+    let search = search::bfs(
+        *source,
+        |key| grid.neighbors4_where(key, |&k| k != '.'),
+        |key| grid.get(key) == Some(&target),
+    );
+    search
+        .targets()
+        .iter()
+        .flat_map(|key| search.all_paths(key))
+        .collect()
 }
 
 #[cfg(test)]
@@ -163,6 +145,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 126384);
+        assert_eq!(part1(INPUT).unwrap(), 126384);
+        assert!(part1("029B").is_err());
     }
 }
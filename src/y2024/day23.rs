@@ -1,36 +1,40 @@
+use crate::error::{parse_lines, Error, Result};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let graph = parse(input);
-    graph
+fn part1(input: &str) -> Result<usize> {
+    let graph = parse(input)?;
+    Ok(graph
         .nodes()
         .into_iter()
         .filter(|node| node.starts_with("t"))
         .flat_map(|node| graph.find_paths(node, node, 3))
         .unique()
-        .count()
+        .count())
 }
 
-fn part2(input: &str) -> String {
-    let graph = parse(input);
-    let mut max_clique = graph.max_clique().unwrap();
+fn part2(input: &str) -> Result<String> {
+    let graph = parse(input)?;
+    let mut max_clique = graph
+        .max_clique()
+        .ok_or_else(|| Error::NoAnswer("no computers in the network".to_string()))?;
     max_clique.sort();
-    max_clique.into_iter().join(",")
+    Ok(max_clique.into_iter().join(","))
 }
 
-fn parse(input: &str) -> Graph {
+fn parse(input: &str) -> Result<Graph> {
     let mut graph = Graph::new();
-    input.lines().for_each(|line| {
-        let parts = line.split("-").collect::<Vec<_>>();
-        graph.add_edge(parts[0], parts[1]);
-    });
-    graph
+    let edges = parse_lines(input, |line| {
+        line.split_once('-')
+            .map(|(a, b)| (a.to_owned(), b.to_owned()))
+            .ok_or_else(|| Error::parse(format!("invalid connection '{}'", line)))
+    })?;
+    for (a, b) in edges {
+        graph.add_edge(&a, &b);
+    }
+    Ok(graph)
 }
 
 #[derive(Debug)]
@@ -55,11 +59,11 @@ impl Graph {
         stack.push(vec![start]);
 
         while let Some(path) = stack.pop() {
-            let prev = path.last().unwrap();
+            let prev = path[path.len() - 1];
             if path.len() > depth {
                 continue;
             }
-            for n in self.nodes[*prev].iter() {
+            for n in self.nodes[prev].iter() {
                 let mut path = path.clone();
                 if n == target && depth == path.len() {
                     path.sort();
@@ -147,11 +151,11 @@ td-yn";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 7);
+        assert_eq!(part1(INPUT).unwrap(), 7);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), "co,de,ka,ta");
+        assert_eq!(part2(INPUT).unwrap(), "co,de,ka,ta");
     }
 }
@@ -6,10 +6,7 @@ lazy_static! {
     static ref ALL: Regex = Regex::new(r"mul\(\d{1,3},\d{1,3}\)|do(?:n't)?\(\)").unwrap();
 }
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2);
 
 fn part1(input: &str) -> usize {
     find_all(input)
@@ -1,11 +1,9 @@
+use crate::error::Result;
 use crate::util::coordinate::Coordinate;
 use crate::util::grid::Grid;
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, Grid::<char>::from_str);
 
 const XMAS: &[char] = &['X', 'M', 'A', 'S'];
 const SAMX: &[char] = &['S', 'A', 'M', 'X'];
@@ -13,9 +11,10 @@ const MAS: &[char] = &['M', 'A', 'S'];
 const SAM: &[char] = &['S', 'A', 'M'];
 const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (-1, 1)];
 
-fn part1(input: &str) -> usize {
-    let grid = Grid::<char>::from_str(input).unwrap();
-    grid.coordinates()
+fn part1(input: &str) -> Result<usize> {
+    let grid = Grid::<char>::from_str(input)?;
+    Ok(grid
+        .coordinates()
         .map(|coordinate| {
             DIRECTIONS
                 .iter()
@@ -23,18 +22,19 @@ fn part1(input: &str) -> usize {
                 .filter(|result| result == XMAS || result == SAMX)
                 .count()
         })
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    let grid = Grid::<char>::from_str(input).unwrap();
-    grid.coordinates()
+fn part2(input: &str) -> Result<usize> {
+    let grid = Grid::<char>::from_str(input)?;
+    Ok(grid
+        .coordinates()
         .filter(|coordinate| is_xmas(&grid, coordinate))
-        .count()
+        .count())
 }
 
 fn is_xmas(grid: &Grid<char>, pos: &Coordinate) -> bool {
-    if *grid.get(pos).unwrap() != 'A' {
+    if grid[*pos] != 'A' {
         return false;
     }
     vec![
@@ -62,7 +62,7 @@ SMSMSASXSS
 SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX";
-        assert_eq!(part1(input), 18);
+        assert_eq!(part1(input).unwrap(), 18);
     }
 
     #[test]
@@ -77,6 +77,6 @@ S.S.S.S.S.
 .A.A.A.A..
 M.M.M.M.M.
 ..........";
-        assert_eq!(part2(input), 9);
+        assert_eq!(part2(input).unwrap(), 9);
     }
 }
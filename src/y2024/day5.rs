@@ -1,40 +1,43 @@
+use crate::error::{parse_lines, Error, Result};
 use std::cmp::Ordering;
+use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
-fn part1(input: &str) -> usize {
-    let (ordering_rules, manual_updates) = parse(input);
-    manual_updates
+fn part1(input: &str) -> Result<usize> {
+    let (ordering_rules, manual_updates) = parse(input)?;
+    Ok(manual_updates
         .into_iter()
         .filter(|update| update.is_sorted(&ordering_rules))
         .map(|update| update.middle_number())
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    let (ordering_rules, manual_updates) = parse(input);
-    manual_updates
+fn part2(input: &str) -> Result<usize> {
+    let (ordering_rules, manual_updates) = parse(input)?;
+    Ok(manual_updates
         .into_iter()
         .filter(|update| !update.is_sorted(&ordering_rules))
         .map(|mut update| {
             update.sort(&ordering_rules);
             update.middle_number()
         })
-        .sum()
+        .sum())
 }
 
-fn parse(input: &str) -> (Vec<PageOrderingRule>, Vec<ManualUpdate>) {
-    let parts = input.split("\n\n").collect::<Vec<_>>();
-    (
-        parts[0]
-            .lines()
-            .map(PageOrderingRule::from)
-            .collect::<Vec<_>>(),
-        parts[1].lines().map(ManualUpdate::from).collect::<Vec<_>>(),
-    )
+fn parse(input: &str) -> Result<(Vec<PageOrderingRule>, Vec<ManualUpdate>)> {
+    let (rules, updates) = input
+        .split_once("\n\n")
+        .ok_or_else(|| Error::parse("expected rules and updates separated by a blank line"))?;
+    let rules = parse_lines(rules, PageOrderingRule::from_str)?;
+    // The updates start after the rules and the blank line
+    let offset = rules.len() + 1;
+    let updates = updates
+        .lines()
+        .enumerate()
+        .map(|(i, line)| ManualUpdate::from_str(line).map_err(|e| e.at_line(offset + i + 1)))
+        .collect::<Result<_>>()?;
+    Ok((rules, updates))
 }
 
 struct PageOrderingRule {
@@ -42,16 +45,17 @@ struct PageOrderingRule {
     right: usize,
 }
 
-impl From<&str> for PageOrderingRule {
-    fn from(value: &str) -> Self {
-        let parts = value
-            .split("|")
-            .filter_map(|x| x.parse::<usize>().ok())
-            .collect::<Vec<_>>();
-        Self {
-            left: parts[0],
-            right: parts[1],
-        }
+impl FromStr for PageOrderingRule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (left, right) = s
+            .split_once('|')
+            .ok_or_else(|| Error::parse(format!("invalid rule '{}'", s)))?;
+        Ok(Self {
+            left: left.parse()?,
+            right: right.parse()?,
+        })
     }
 }
 
@@ -87,14 +91,19 @@ impl ManualUpdate {
     }
 }
 
-impl From<&str> for ManualUpdate {
-    fn from(value: &str) -> Self {
-        Self {
-            pages: value
-                .split(",")
-                .filter_map(|n| n.parse().ok())
-                .collect::<Vec<_>>(),
+impl FromStr for ManualUpdate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(Error::parse("empty update"));
         }
+        Ok(Self {
+            pages: s
+                .split(',')
+                .map(str::parse)
+                .collect::<std::result::Result<_, _>>()?,
+        })
     }
 }
 
@@ -133,11 +142,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 143);
+        assert_eq!(part1(INPUT).unwrap(), 143);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 123);
+        assert_eq!(part2(INPUT).unwrap(), 123);
     }
 }
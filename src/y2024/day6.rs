@@ -1,29 +1,33 @@
+use crate::error::{Error, Result};
 use crate::util::coordinate::Coordinate;
 use crate::util::dir::Direction;
 use crate::util::grid::Grid;
 use std::collections::HashSet;
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, Grid::<char>::from_str);
 
-fn part1(input: &str) -> usize {
-    let grid = Grid::<char>::from_str(input).unwrap();
-    let guard = Guard::new(grid.find(&'^').unwrap(), Direction::new(-1, 0));
+fn part1(input: &str) -> Result<usize> {
+    let grid = Grid::<char>::from_str(input)?;
+    let guard = Guard::new(
+        grid.find(&'^')
+            .ok_or_else(|| Error::parse("missing guard"))?,
+        Direction::new(-1, 0),
+    );
     let visited = find_path(&grid, guard);
-    visited.len()
+    Ok(visited.len())
 }
 
-fn part2(input: &str) -> usize {
-    let mut grid = Grid::<char>::from_str(input).unwrap();
-    let start = grid.find(&'^').unwrap();
+fn part2(input: &str) -> Result<usize> {
+    let mut grid = Grid::<char>::from_str(input)?;
+    let start = grid
+        .find(&'^')
+        .ok_or_else(|| Error::parse("missing guard"))?;
     let start_dir = Direction::new(-1, 0);
     let visited = find_path(&grid, Guard::new(start, start_dir));
 
     let coordinates = grid.coordinates().collect::<Vec<_>>();
-    coordinates
+    Ok(coordinates
         .iter()
         .filter(|coordinate| {
             if !visited.contains(coordinate) {
@@ -38,7 +42,7 @@ fn part2(input: &str) -> usize {
                 None => unreachable!(),
             }
         })
-        .count()
+        .count())
 }
 
 fn find_path(grid: &Grid<char>, mut guard: Guard) -> HashSet<Coordinate> {
@@ -56,10 +60,8 @@ fn is_infinite_loop(grid: &Grid<char>, mut guard: Guard) -> bool {
     let mut visited = HashSet::new();
     loop {
         match guard.walk(grid) {
-            Movement::Rotate => {
-                if !visited.insert((guard.current, guard.dir)) {
-                    return true;
-                };
+            Movement::Rotate if !visited.insert((guard.current, guard.dir)) => {
+                return true;
             }
             Movement::ExitingArea => {
                 return false;
@@ -121,11 +123,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 41);
+        assert_eq!(part1(INPUT).unwrap(), 41);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 6);
+        assert_eq!(part2(INPUT).unwrap(), 6);
     }
 }
@@ -1,28 +1,25 @@
-use std::error::Error;
+use crate::error::{parse_lines, Error, Result};
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, parse);
 
 type Equation = (usize, Vec<usize>);
 
-fn part1(input: &str) -> usize {
-    parse(input)
-        .unwrap()
+fn part1(input: &str) -> Result<usize> {
+    let equations = parse(input)?;
+    Ok(equations
         .into_iter()
         .filter(|(value, operands)| evaluate(*value, operands, 0, false))
         .map(|(value, _)| value)
-        .sum()
+        .sum())
 }
 
-fn part2(input: &str) -> usize {
-    parse(input)
-        .unwrap()
+fn part2(input: &str) -> Result<usize> {
+    let equations = parse(input)?;
+    Ok(equations
         .into_iter()
         .filter(|(value, operands)| evaluate(*value, operands, 0, true))
         .map(|(value, _)| value)
-        .sum()
+        .sum())
 }
 
 fn evaluate(value: usize, operands: &[usize], current: usize, concat_enabled: bool) -> bool {
@@ -55,20 +52,17 @@ fn concat(a: usize, b: usize) -> usize {
     a * 10usize.pow(digits) + b
 }
 
-fn parse(input: &str) -> Result<Vec<Equation>, Box<dyn Error>> {
-    Ok(input
-        .lines()
-        .filter_map(|line| {
-            let mut parts = line.split(":");
-            let value = parts.next().and_then(|n| n.parse::<usize>().ok());
-            let operands = parts.next().map(|raw| {
-                raw.split_whitespace()
-                    .filter_map(|w| w.parse::<usize>().ok())
-                    .collect::<Vec<_>>()
-            });
-            value.zip(operands)
-        })
-        .collect::<Vec<_>>())
+fn parse(input: &str) -> Result<Vec<Equation>> {
+    parse_lines(input, |line| -> Result<Equation> {
+        let (value, operands) = line
+            .split_once(":")
+            .ok_or_else(|| Error::parse("missing ':'"))?;
+        let operands = operands
+            .split_whitespace()
+            .map(|w| w.parse::<usize>())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok((value.parse()?, operands))
+    })
 }
 
 #[cfg(test)]
@@ -86,11 +80,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 3749);
+        assert_eq!(part1(INPUT).unwrap(), 3749);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 11387);
+        assert_eq!(part2(INPUT).unwrap(), 11387);
     }
 }
@@ -1,17 +1,15 @@
+use crate::error::Result;
 use crate::util::coordinate::Coordinate;
 use crate::util::grid::Grid;
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, Grid::<char>::from_str);
 
-fn part1(input: &str) -> usize {
-    let grid = Grid::<char>::from_str(input).unwrap();
-    antennas_by_frequency(&grid)
+fn part1(input: &str) -> Result<usize> {
+    let grid = Grid::<char>::from_str(input)?;
+    Ok(antennas_by_frequency(&grid)
         .values()
         .flat_map(|antennas| {
             antennas.iter().combinations(2).flat_map(|pair| {
@@ -21,12 +19,12 @@ fn part1(input: &str) -> usize {
         })
         .filter(|antenna| grid.contains(antenna))
         .unique()
-        .count()
+        .count())
 }
 
-fn part2(input: &str) -> usize {
-    let grid = Grid::<char>::from_str(input).unwrap();
-    antennas_by_frequency(&grid)
+fn part2(input: &str) -> Result<usize> {
+    let grid = Grid::<char>::from_str(input)?;
+    Ok(antennas_by_frequency(&grid)
         .values()
         .flat_map(|antennas| {
             antennas.iter().combinations(2).flat_map(|pair| {
@@ -39,7 +37,7 @@ fn part2(input: &str) -> usize {
             })
         })
         .unique()
-        .count()
+        .count())
 }
 
 fn trajectory(start: Coordinate, direction: Coordinate, grid: &Grid<char>) -> Vec<Coordinate> {
@@ -81,11 +79,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 14);
+        assert_eq!(part1(INPUT).unwrap(), 14);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 34);
+        assert_eq!(part2(INPUT).unwrap(), 34);
     }
 }
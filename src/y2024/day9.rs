@@ -1,23 +1,21 @@
+use crate::error::{Error, Result};
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::mem::swap;
 use std::str::FromStr;
 
-pub fn solve(input: &str) {
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-}
+crate::solution!(part1, part2, DiskMap::from_str);
 
-fn part1(input: &str) -> usize {
-    let mut map = DiskMap::from_str(input).expect("Failed to parse input");
+fn part1(input: &str) -> Result<usize> {
+    let mut map = DiskMap::from_str(input)?;
     map.compact(true);
-    map.checksum()
+    Ok(map.checksum())
 }
 
-fn part2(input: &str) -> usize {
-    let mut map = DiskMap::from_str(input).expect("Failed to parse input");
+fn part2(input: &str) -> Result<usize> {
+    let mut map = DiskMap::from_str(input)?;
     map.compact(false);
-    map.checksum()
+    Ok(map.checksum())
 }
 
 #[derive(Debug)]
@@ -104,7 +102,7 @@ fn move_file_into_block(free_block: &mut Block, file: &mut Block) -> Option<Bloc
             Some(Block::new_file(
                 free_block.start,
                 tmp,
-                file.file_id.unwrap(),
+                file.file_id.expect("only files are moved"),
             ))
         }
         Ordering::Equal => {
@@ -121,12 +119,10 @@ fn move_file_into_block(free_block: &mut Block, file: &mut Block) -> Option<Bloc
     }
 }
 
-#[derive(Debug)]
-struct ParseDiskMapError {}
 impl FromStr for DiskMap {
-    type Err = ParseDiskMapError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let mut pos = 0;
         let mut id = 0;
         let blocks: Vec<Block> = parse_blocks(s)?
@@ -151,14 +147,14 @@ impl FromStr for DiskMap {
     }
 }
 
-fn parse_blocks(s: &str) -> Result<Vec<(usize, u32)>, ParseDiskMapError> {
+fn parse_blocks(s: &str) -> Result<Vec<(usize, u32)>> {
     s.trim()
         .chars()
         .enumerate()
         .map(|(i, c)| {
             c.to_digit(10)
                 .map(|len| (i, len))
-                .ok_or(ParseDiskMapError {})
+                .ok_or_else(|| Error::parse(format!("invalid digit '{}'", c)))
         })
         .collect()
 }
@@ -171,11 +167,11 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 1928);
+        assert_eq!(part1(INPUT).unwrap(), 1928);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 2858);
+        assert_eq!(part2(INPUT).unwrap(), 2858);
     }
 }
@@ -0,0 +1,30 @@
+use aoc_rust::solution;
+use aoc_rust::verify::{self, Verification};
+
+// Days whose input can't be solved, so they have no accepted answers to compare with.
+// inputs/2024/10.in is corrupted: it's a single 19999 digit line instead of the square
+// topographic map, so both parts find no trailheads.
+const WITHOUT_ANSWERS: &[(u16, u8)] = &[(2024, 10)];
+
+// Runs a registered day against `inputs/<YEAR>/<DAY>.in` and compares with `<DAY>.ans`
+fn verify(year: u16, day: u8) {
+    let entry = solution::find(year, day).unwrap();
+    let verification = verify::verify(entry).unwrap();
+    if WITHOUT_ANSWERS.contains(&(year, day)) {
+        assert_eq!(
+            verification,
+            Verification::MissingAnswers,
+            "{} {}",
+            year,
+            day
+        );
+        return;
+    }
+    match verification {
+        Verification::Failed(mismatches) => panic!("{} {}: {:?}", year, day, mismatches),
+        Verification::MissingAnswers => panic!("{} {}: no answers to compare with", year, day),
+        Verification::Passed | Verification::MissingInput => {}
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/answer_tests.rs"));
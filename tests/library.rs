@@ -0,0 +1,21 @@
+use aoc_rust::answer::Answer;
+use aoc_rust::solution;
+use aoc_rust::util::coordinate::Coordinate;
+use aoc_rust::util::dir::Direction;
+use aoc_rust::util::grid::Grid;
+
+#[test]
+fn test_grid_toolkit() {
+    let grid: Grid<char> = "S.#\n..E".parse().unwrap();
+    let start = grid.find(&'S').unwrap();
+    assert_eq!(start, Coordinate::new(0, 0));
+    assert_eq!(grid.get(&start.offset(&Direction::RIGHT)), Some(&'.'));
+    assert_eq!(grid.find(&'E'), Some(Coordinate::new(1, 2)));
+}
+
+#[test]
+fn test_solution_from_registry() {
+    let entry = solution::find(2015, 1).unwrap();
+    assert_eq!(entry.solution.part1("(()(()(").unwrap(), Answer::Number(3));
+    assert_eq!(entry.solution.part2("()())").unwrap(), Answer::Number(5));
+}